@@ -1,10 +1,10 @@
 use crate::{
-    revm_primitives::{AccountInfo, Log},
+    revm_primitives::{AccountInfo, Log, SpecId},
     Account, Address, Log as RethLog, TransactionKind, KECCAK_EMPTY, U256,
 };
 use revm::{
     interpreter::gas::initial_tx_gas,
-    primitives::{MergeSpec, ShanghaiSpec},
+    primitives::{BerlinSpec, FrontierSpec, HomesteadSpec, IstanbulSpec, MergeSpec, ShanghaiSpec},
 };
 
 /// Check equality between Revm and Reth `Log`s.
@@ -59,3 +59,63 @@ pub fn calculate_intrinsic_gas_after_merge(
         initial_tx_gas::<MergeSpec>(input, kind.is_create(), access_list)
     }
 }
+
+/// Calculates the Intrinsic Gas usage for a Transaction under the rules of the given [SpecId].
+///
+/// The intrinsic gas only changes with Homestead (contract creation), Istanbul (calldata), Berlin
+/// (access lists) and Shanghai (initcode), so the spec is mapped to the latest of these.
+#[inline]
+pub fn calculate_intrinsic_gas(
+    input: &[u8],
+    kind: &TransactionKind,
+    access_list: &[(Address, Vec<U256>)],
+    spec_id: SpecId,
+) -> u64 {
+    let is_create = kind.is_create();
+    if spec_id >= SpecId::SHANGHAI {
+        initial_tx_gas::<ShanghaiSpec>(input, is_create, access_list)
+    } else if spec_id >= SpecId::BERLIN {
+        initial_tx_gas::<BerlinSpec>(input, is_create, access_list)
+    } else if spec_id >= SpecId::ISTANBUL {
+        initial_tx_gas::<IstanbulSpec>(input, is_create, access_list)
+    } else if spec_id >= SpecId::HOMESTEAD {
+        initial_tx_gas::<HomesteadSpec>(input, is_create, access_list)
+    } else {
+        initial_tx_gas::<FrontierSpec>(input, is_create, access_list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intrinsic_gas_by_spec() {
+        let call = TransactionKind::Call(Address::ZERO);
+        // one non-zero and one zero calldata byte
+        let input = [1, 0];
+        let access_list = vec![(Address::ZERO, vec![U256::ZERO])];
+
+        // non-zero calldata bytes cost 68 gas before Istanbul
+        assert_eq!(calculate_intrinsic_gas(&input, &call, &[], SpecId::BYZANTIUM), 21_000 + 68 + 4);
+        assert_eq!(calculate_intrinsic_gas(&input, &call, &[], SpecId::ISTANBUL), 21_000 + 16 + 4);
+
+        // access lists are only charged since Berlin
+        assert_eq!(calculate_intrinsic_gas(&[], &call, &access_list, SpecId::ISTANBUL), 21_000);
+        assert_eq!(
+            calculate_intrinsic_gas(&[], &call, &access_list, SpecId::BERLIN),
+            21_000 + 2_400 + 1_900
+        );
+
+        // contract creation is only charged extra since Homestead
+        let create = TransactionKind::Create;
+        assert_eq!(calculate_intrinsic_gas(&[], &create, &[], SpecId::FRONTIER), 21_000);
+        assert_eq!(calculate_intrinsic_gas(&[], &create, &[], SpecId::HOMESTEAD), 53_000);
+
+        // matches the post merge rules after the merge
+        assert_eq!(
+            calculate_intrinsic_gas(&input, &call, &access_list, SpecId::SHANGHAI),
+            calculate_intrinsic_gas_after_merge(&input, &call, &access_list, true)
+        );
+    }
+}
@@ -13,6 +13,10 @@ use std::collections::{BTreeSet, HashMap, HashSet};
 pub struct AccessListInspector {
     /// All addresses that should be excluded from the final accesslist
     excluded: HashSet<Address>,
+    /// All precompile addresses, which are always excluded from the final accesslist
+    precompiles: HashSet<Address>,
+    /// Precompiles that were accessed during execution, and therefore excluded
+    accessed_precompiles: BTreeSet<Address>,
     /// All addresses and touched slots
     access_list: HashMap<Address, BTreeSet<B256>>,
 }
@@ -27,8 +31,11 @@ impl AccessListInspector {
         to: Address,
         precompiles: impl IntoIterator<Item = Address>,
     ) -> Self {
+        let precompiles: HashSet<Address> = precompiles.into_iter().collect();
         AccessListInspector {
-            excluded: [from, to].into_iter().chain(precompiles).collect(),
+            excluded: [from, to].into_iter().chain(precompiles.iter().copied()).collect(),
+            precompiles,
+            accessed_precompiles: BTreeSet::new(),
             access_list: access_list
                 .0
                 .into_iter()
@@ -56,6 +63,23 @@ impl AccessListInspector {
         });
         AccessList(items.collect())
     }
+
+    /// Returns the precompile addresses that were accessed during execution but are excluded from
+    /// the access list, sorted by address.
+    pub fn accessed_precompiles(&self) -> Vec<Address> {
+        self.accessed_precompiles.iter().copied().collect()
+    }
+
+    /// Records an account access, skipping excluded addresses.
+    fn touch_account(&mut self, addr: Address) {
+        if self.excluded.contains(&addr) {
+            if self.precompiles.contains(&addr) {
+                self.accessed_precompiles.insert(addr);
+            }
+        } else {
+            self.access_list.entry(addr).or_default();
+        }
+    }
 }
 
 impl<DB> Inspector<DB> for AccessListInspector
@@ -80,17 +104,13 @@ where
             opcode::SELFDESTRUCT => {
                 if let Ok(slot) = interpreter.stack().peek(0) {
                     let addr = Address::from_word(B256::from(slot.to_be_bytes()));
-                    if !self.excluded.contains(&addr) {
-                        self.access_list.entry(addr).or_default();
-                    }
+                    self.touch_account(addr);
                 }
             }
             opcode::DELEGATECALL | opcode::CALL | opcode::STATICCALL | opcode::CALLCODE => {
                 if let Ok(slot) = interpreter.stack().peek(1) {
                     let addr = Address::from_word(B256::from(slot.to_be_bytes()));
-                    if !self.excluded.contains(&addr) {
-                        self.access_list.entry(addr).or_default();
-                    }
+                    self.touch_account(addr);
                 }
             }
             _ => (),
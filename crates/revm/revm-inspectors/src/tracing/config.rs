@@ -21,6 +21,10 @@ pub struct TracingInspectorConfig {
     pub record_call_return_data: bool,
     /// Whether to record logs
     pub record_logs: bool,
+    /// Maximum number of opcode level steps to record, unlimited if `None`.
+    pub max_steps: Option<usize>,
+    /// Maximum number of memory bytes to record across all memory snapshots, unlimited if `None`.
+    pub max_memory_bytes: Option<usize>,
 }
 
 impl TracingInspectorConfig {
@@ -34,6 +38,8 @@ impl TracingInspectorConfig {
             exclude_precompile_calls: false,
             record_call_return_data: false,
             record_logs: true,
+            max_steps: None,
+            max_memory_bytes: None,
         }
     }
 
@@ -49,6 +55,8 @@ impl TracingInspectorConfig {
             exclude_precompile_calls: true,
             record_call_return_data: false,
             record_logs: false,
+            max_steps: None,
+            max_memory_bytes: None,
         }
     }
 
@@ -64,6 +72,8 @@ impl TracingInspectorConfig {
             exclude_precompile_calls: false,
             record_call_return_data: false,
             record_logs: false,
+            max_steps: None,
+            max_memory_bytes: None,
         }
     }
 
@@ -149,6 +159,24 @@ impl TracingInspectorConfig {
         self.record_logs = record_logs;
         self
     }
+
+    /// Configure the maximum number of steps the tracer records.
+    ///
+    /// Once exceeded, the tracer aborts the execution, see
+    /// [TracingInspector::is_limit_exceeded](crate::tracing::TracingInspector::is_limit_exceeded).
+    pub fn set_max_steps(mut self, max_steps: Option<usize>) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Configure the maximum number of memory bytes the tracer records in memory snapshots.
+    ///
+    /// Once exceeded, the tracer aborts the execution, see
+    /// [TracingInspector::is_limit_exceeded](crate::tracing::TracingInspector::is_limit_exceeded).
+    pub fn set_max_memory_bytes(mut self, max_memory_bytes: Option<usize>) -> Self {
+        self.max_memory_bytes = max_memory_bytes;
+        self
+    }
 }
 
 /// How much of the stack to record. Nothing, just the items pushed, or the full stack
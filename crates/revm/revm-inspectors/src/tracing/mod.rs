@@ -65,6 +65,12 @@ pub struct TracingInspector {
     ///
     /// This is filled during execution.
     spec_id: Option<SpecId>,
+    /// Number of steps recorded so far.
+    recorded_steps: usize,
+    /// Number of memory bytes recorded in memory snapshots so far.
+    recorded_memory_bytes: usize,
+    /// Whether the execution was aborted because a configured limit was exceeded.
+    limit_exceeded: bool,
 }
 
 // === impl TracingInspector ===
@@ -80,9 +86,21 @@ impl TracingInspector {
             last_call_return_data: None,
             gas_inspector: Default::default(),
             spec_id: None,
+            recorded_steps: 0,
+            recorded_memory_bytes: 0,
+            limit_exceeded: false,
         }
     }
 
+    /// Returns `true` if the execution was aborted because the configured
+    /// [TracingInspectorConfig::max_steps] or [TracingInspectorConfig::max_memory_bytes] was
+    /// exceeded.
+    ///
+    /// If this is `true` the recorded traces are incomplete.
+    pub fn is_limit_exceeded(&self) -> bool {
+        self.limit_exceeded
+    }
+
     /// Gets a reference to the recorded call traces.
     pub fn get_traces(&self) -> &CallTraceArena {
         &self.traces
@@ -126,6 +144,15 @@ impl TracingInspector {
         GethTraceBuilder::new(self.traces.arena, self.config)
     }
 
+    /// Returns `true` if recording another step with a memory snapshot of the given size would
+    /// exceed the configured step or memory limit.
+    fn exceeds_limits(&self, memory_bytes: usize) -> bool {
+        self.config.max_steps.is_some_and(|max| self.recorded_steps + 1 > max) ||
+            self.config
+                .max_memory_bytes
+                .is_some_and(|max| self.recorded_memory_bytes + memory_bytes > max)
+    }
+
     /// Returns true if we're no longer in the context of the root call.
     fn is_deep(&self) -> bool {
         // the root call will always be the first entry in the trace stack
@@ -293,6 +320,9 @@ impl TracingInspector {
             .record_memory_snapshots
             .then(|| RecordedMemory::new(interp.shared_memory.context_memory().to_vec()))
             .unwrap_or_default();
+        self.recorded_steps += 1;
+        self.recorded_memory_bytes += memory.len();
+
         let stack = if self.config.record_stack_snapshots.is_full() {
             Some(interp.stack.data().clone())
         } else {
@@ -402,6 +432,17 @@ where
 
     fn step(&mut self, interp: &mut Interpreter<'_>, data: &mut EVMData<'_, DB>) {
         if self.config.record_steps {
+            let memory_bytes = if self.config.record_memory_snapshots {
+                interp.shared_memory.context_memory().len()
+            } else {
+                0
+            };
+            if self.limit_exceeded || self.exceeds_limits(memory_bytes) {
+                // abort instead of recording an unbounded number of steps
+                self.limit_exceeded = true;
+                interp.instruction_result = InstructionResult::Revert;
+                return
+            }
             self.gas_inspector.step(interp, data);
             self.start_step(interp, data);
         }
@@ -72,6 +72,9 @@ where
         eth_cache.clone(),
         gas_oracle,
         EthConfig::default().rpc_gas_cap,
+        EthConfig::default().tracing_limits,
+        EthConfig::default().max_recovered_blocks,
+        EthConfig::default().sequencer_retry,
         Box::new(executor.clone()),
         BlockingTaskPool::build().expect("failed to build tracing pool"),
         fee_history_cache,
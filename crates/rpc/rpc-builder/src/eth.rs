@@ -5,7 +5,8 @@ use reth_rpc::{
     eth::{
         cache::{EthStateCache, EthStateCacheConfig},
         gas_oracle::GasPriceOracleConfig,
-        EthFilterConfig, FeeHistoryCacheConfig, RPC_DEFAULT_GAS_CAP,
+        EthFilterConfig, FeeHistoryCacheConfig, SequencerRetryConfig, TracingLimits,
+        DEFAULT_MAX_RECOVERED_BLOCKS, RPC_DEFAULT_GAS_CAP,
     },
     BlockingTaskPool, EthApi, EthFilter, EthPubSub,
 };
@@ -48,6 +49,14 @@ pub struct EthConfig {
     pub stale_filter_ttl: std::time::Duration,
     /// Settings for the fee history cache
     pub fee_history_cache: FeeHistoryCacheConfig,
+    /// Limits for tracing RPC methods.
+    pub tracing_limits: TracingLimits,
+    /// Maximum number of blocks for which the ecrecovered transactions are cached for tracing.
+    ///
+    /// Defaults to [DEFAULT_MAX_RECOVERED_BLOCKS]
+    pub max_recovered_blocks: u32,
+    /// Retry behaviour for forwarding transactions to the sequencer.
+    pub sequencer_retry: SequencerRetryConfig,
 }
 
 impl EthConfig {
@@ -74,6 +83,9 @@ impl Default for EthConfig {
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
             fee_history_cache: FeeHistoryCacheConfig::default(),
+            tracing_limits: TracingLimits::default(),
+            max_recovered_blocks: DEFAULT_MAX_RECOVERED_BLOCKS,
+            sequencer_retry: SequencerRetryConfig::default(),
         }
     }
 }
@@ -114,4 +126,22 @@ impl EthConfig {
         self.rpc_gas_cap = rpc_gas_cap;
         self
     }
+
+    /// Configures the step, memory and time limits for tracing RPC methods
+    pub fn tracing_limits(mut self, tracing_limits: TracingLimits) -> Self {
+        self.tracing_limits = tracing_limits;
+        self
+    }
+
+    /// Configures the maximum number of blocks for which the ecrecovered transactions are cached
+    pub fn max_recovered_blocks(mut self, max_blocks: u32) -> Self {
+        self.max_recovered_blocks = max_blocks;
+        self
+    }
+
+    /// Configures the retry behaviour for forwarding transactions to the sequencer
+    pub fn sequencer_retry(mut self, sequencer_retry: SequencerRetryConfig) -> Self {
+        self.sequencer_retry = sequencer_retry;
+        self
+    }
 }
@@ -1206,6 +1206,9 @@ where
                 cache.clone(),
                 gas_oracle,
                 self.config.eth.rpc_gas_cap,
+                self.config.eth.tracing_limits,
+                self.config.eth.max_recovered_blocks,
+                self.config.eth.sequencer_retry,
                 executor.clone(),
                 blocking_task_pool.clone(),
                 fee_history_cache,
@@ -155,6 +155,7 @@ where
         .await
         .unwrap_err();
     EthApiClient::transaction_by_hash(client, tx_hash).await.unwrap();
+    EthApiClient::raw_transaction_by_hash(client, tx_hash).await.unwrap();
     EthApiClient::transaction_by_block_hash_and_index(client, hash, index).await.unwrap();
     EthApiClient::transaction_by_block_number_and_index(client, block_number, index).await.unwrap();
     EthApiClient::create_access_list(client, call_request.clone(), Some(block_number.into()))
@@ -56,6 +56,9 @@ pub struct AccessListWithGasUsed {
     pub access_list: AccessList,
     /// Estimated gas used with access list.
     pub gas_used: U256,
+    /// Precompiles that were accessed during execution, but are excluded from the access list.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excluded_precompiles: Vec<Address>,
 }
 
 #[cfg(test)]
@@ -81,6 +84,7 @@ mod tests {
                 AccessListItem { address: Address::ZERO, storage_keys: vec![B256::ZERO] },
             ]),
             gas_used: U256::from(100),
+            excluded_precompiles: vec![Address::with_last_byte(1)],
         };
         let json = serde_json::to_string(&list).unwrap();
         let list2 = serde_json::from_str::<AccessListWithGasUsed>(&json).unwrap();
@@ -66,6 +66,11 @@ impl BlockingTaskPool {
         rayon::ThreadPoolBuilder::new()
     }
 
+    /// Returns the number of threads of the threadpool.
+    pub fn num_threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+
     /// Convenience function to build a new threadpool with the default configuration.
     ///
     /// Uses [`rayon::ThreadPoolBuilder::build`](rayon::ThreadPoolBuilder::build) defaults but
@@ -1,20 +1,32 @@
 //! Contains RPC handler implementations specific to blocks.
 
+use super::transactions::StateCacheDB;
 use crate::{
     eth::{
-        api::transactions::build_transaction_receipt_with_block_receipts,
         error::{EthApiError, EthResult},
+        revm_utils::transact,
+        EthTransactions,
     },
     EthApi,
 };
 use reth_network_api::NetworkInfo;
-use reth_primitives::{BlockId, TransactionMeta};
+use reth_primitives::{
+    revm::{compat::into_reth_log, env::tx_env_with_recovered},
+    revm_primitives::{db::DatabaseCommit, Env, ExecutionResult, ResultAndState},
+    Address, BlockId, BlockNumber, SealedHeader, TransactionSignedEcRecovered, B256, U256,
+};
 
 use reth_provider::{BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
-use reth_rpc_types::{Index, RichBlock, TransactionReceipt};
+use reth_revm::{database::StateProviderDatabase, tracing::TracingInspectorConfig};
+use reth_rpc_types::{Index, RichBlock};
 
 use reth_rpc_types_compat::block::{from_block, uncle_block_from_header};
 use reth_transaction_pool::TransactionPool;
+use revm::db::CacheDB;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 impl<Provider, Pool, Network> EthApi<Provider, Pool, Network>
 where
@@ -55,70 +67,6 @@ where
         Ok(uncle)
     }
 
-    /// Returns all transaction receipts in the block.
-    ///
-    /// Returns `None` if the block wasn't found.
-    pub(crate) async fn block_receipts(
-        &self,
-        block_id: BlockId,
-    ) -> EthResult<Option<Vec<TransactionReceipt>>> {
-        let mut block_and_receipts = None;
-
-        if block_id.is_pending() {
-            block_and_receipts = self.provider().pending_block_and_receipts()?;
-        } else if let Some(block_hash) = self.provider().block_hash_for_id(block_id)? {
-            block_and_receipts = self.cache().get_block_and_receipts(block_hash).await?;
-        }
-
-        if let Some((block, receipts)) = block_and_receipts {
-            let block_number = block.number;
-            let base_fee = block.base_fee_per_gas;
-            let block_hash = block.hash;
-            let excess_blob_gas = block.excess_blob_gas;
-
-            #[cfg(feature = "optimism")]
-            let (block_timestamp, l1_block_info) = {
-                let body = reth_revm::optimism::parse_l1_info_tx(
-                    &block.body.first().ok_or(EthApiError::InternalEthError)?.input()[4..],
-                );
-                (block.timestamp, body.ok())
-            };
-
-            let receipts = block
-                .body
-                .into_iter()
-                .zip(receipts.clone())
-                .enumerate()
-                .map(|(idx, (tx, receipt))| {
-                    let meta = TransactionMeta {
-                        tx_hash: tx.hash,
-                        index: idx as u64,
-                        block_hash,
-                        block_number,
-                        base_fee,
-                        excess_blob_gas,
-                    };
-
-                    #[cfg(feature = "optimism")]
-                    let op_tx_meta =
-                        self.build_op_tx_meta(&tx, l1_block_info.clone(), block_timestamp)?;
-
-                    build_transaction_receipt_with_block_receipts(
-                        tx,
-                        meta,
-                        receipt,
-                        &receipts,
-                        #[cfg(feature = "optimism")]
-                        op_tx_meta,
-                    )
-                })
-                .collect::<EthResult<Vec<_>>>();
-            return receipts.map(Some)
-        }
-
-        Ok(None)
-    }
-
     /// Returns the number transactions in the given block.
     ///
     /// Returns `None` if the block does not exist
@@ -176,6 +124,85 @@ where
         Ok(self.cache().get_sealed_block_with_senders(block_hash).await?)
     }
 
+    /// Returns the header and the ecrecovered transactions of the block for the given block id.
+    ///
+    /// The transactions of non-pending blocks are cached by block hash, so that replaying the same
+    /// block repeatedly doesn't recover the signers of its transactions again.
+    pub(crate) async fn block_with_recovered_transactions(
+        &self,
+        block_id: impl Into<BlockId>,
+    ) -> EthResult<Option<(SealedHeader, Arc<Vec<TransactionSignedEcRecovered>>)>> {
+        let block_id = block_id.into();
+
+        if block_id.is_pending() {
+            let Some(block) = self.block_with_senders(block_id).await? else { return Ok(None) };
+            let header = block.header.clone();
+            return Ok(Some((header, Arc::new(block.into_transactions_ecrecovered().collect()))))
+        }
+
+        let block_hash = match self.provider().block_hash_for_id(block_id)? {
+            Some(block_hash) => block_hash,
+            None => return Ok(None),
+        };
+
+        if let Some(transactions) = self.cached_recovered_transactions(block_hash).await {
+            if let Some(header) = self.provider().header(&block_hash)? {
+                return Ok(Some((header.seal(block_hash), transactions)))
+            }
+        }
+
+        let Some(block) = self.cache().get_sealed_block_with_senders(block_hash).await? else {
+            return Ok(None)
+        };
+        let header = block.header.clone();
+        let transactions = Arc::new(block.into_transactions_ecrecovered().collect::<Vec<_>>());
+        self.cache_recovered_transactions(block_hash, Arc::clone(&transactions)).await;
+
+        Ok(Some((header, transactions)))
+    }
+
+    /// Replays all transactions of the block on top of the state of its parent, on a new task,
+    /// and returns what `f` returned for every transaction, in block order.
+    ///
+    /// `f` is called with the state, the [Env] of the transaction and the transaction itself and
+    /// is responsible for executing the transaction and committing its state changes.
+    ///
+    /// Returns `None` if the block does not exist.
+    pub(crate) async fn spawn_replay_block<F, R>(
+        &self,
+        block_id: BlockId,
+        mut f: F,
+    ) -> EthResult<Option<Vec<R>>>
+    where
+        F: FnMut(&mut StateCacheDB, Env, &TransactionSignedEcRecovered) -> EthResult<R>
+            + Send
+            + 'static,
+        R: Send + 'static,
+    {
+        let ((cfg, block_env, _), block) = futures::try_join!(
+            self.evm_env_at(block_id),
+            self.block_with_recovered_transactions(block_id)
+        )?;
+        let Some((header, transactions)) = block else { return Ok(None) };
+
+        self.spawn_with_state_at_block(header.parent_hash.into(), move |state| {
+            let mut db = CacheDB::new(StateProviderDatabase::new(state));
+            transactions
+                .iter()
+                .map(|tx| {
+                    let env = Env {
+                        cfg: cfg.clone(),
+                        block: block_env.clone(),
+                        tx: tx_env_with_recovered(tx),
+                    };
+                    f(&mut db, env, tx)
+                })
+                .collect()
+        })
+        .await
+        .map(Some)
+    }
+
     /// Returns the populated rpc block object for the given block id.
     ///
     /// If `full` is true, the block object will contain all transaction objects, otherwise it will
@@ -197,4 +224,1116 @@ where
         let block = from_block(block.unseal(), total_difficulty, full.into(), Some(block_hash))?;
         Ok(Some(block.into()))
     }
+
+    /// Returns the total amount of ETH burned in the given block, which is the base fee per gas
+    /// times the gas used by all transactions in the block.
+    ///
+    /// Returns `None` if the block does not exist or predates London.
+    pub async fn block_eth_burned(&self, block_id: BlockId) -> EthResult<Option<U256>> {
+        let Some(header) = self.provider().header_by_id(block_id)? else { return Ok(None) };
+        Ok(header
+            .base_fee_per_gas
+            .map(|base_fee| U256::from(base_fee) * U256::from(header.gas_used)))
+    }
+
+    /// Returns statistics of the gas used by the transactions in the given block.
+    ///
+    /// Returns `None` if the block does not exist.
+    pub async fn block_gas_stats(&self, block_id: BlockId) -> EthResult<Option<GasStats>> {
+        let Some(receipts) = self.block_receipts(block_id).await? else { return Ok(None) };
+        let mut gas_used = receipts
+            .iter()
+            .map(|receipt| receipt.gas_used.unwrap_or_default().to::<u64>())
+            .collect::<Vec<_>>();
+        gas_used.sort_unstable();
+        Ok(Some(GasStats::from_sorted(&gas_used)))
+    }
+
+    /// Returns the effective gas price of every transaction in the given block, sorted in
+    /// descending order.
+    ///
+    /// Returns `None` if the block does not exist.
+    pub async fn block_effective_gas_prices(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<Vec<U256>>> {
+        let Some(block) = self.block(block_id).await? else { return Ok(None) };
+        let mut prices = block
+            .body
+            .iter()
+            .map(|tx| U256::from(tx.effective_gas_price(block.base_fee_per_gas)))
+            .collect::<Vec<_>>();
+        prices.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(Some(prices))
+    }
+
+    /// Returns the median effective gas price of the transactions in the given block.
+    ///
+    /// For an even number of transactions this is the mean of the two middle prices, rounded down.
+    ///
+    /// Returns `None` if the block does not exist or has no transactions.
+    pub async fn block_median_gas_price(&self, block_id: BlockId) -> EthResult<Option<U256>> {
+        let Some(prices) = self.block_effective_gas_prices(block_id).await? else {
+            return Ok(None)
+        };
+        if prices.is_empty() {
+            return Ok(None)
+        }
+        let mid = prices.len() / 2;
+        let median = if prices.len() % 2 == 0 {
+            (prices[mid - 1] + prices[mid]) / U256::from(2)
+        } else {
+            prices[mid]
+        };
+        Ok(Some(median))
+    }
+
+    /// Returns the total amount of all withdrawals in the given block in wei.
+    ///
+    /// Returns zero for blocks before Shanghai and `None` if the block does not exist.
+    pub async fn block_withdrawal_total(&self, block_id: BlockId) -> EthResult<Option<U256>> {
+        let Some(block) = self.block(block_id).await? else { return Ok(None) };
+        let total = block
+            .withdrawals
+            .iter()
+            .flatten()
+            .fold(U256::ZERO, |total, withdrawal| total + U256::from(withdrawal.amount_wei()));
+        Ok(Some(total))
+    }
+
+    /// Returns the transaction fees paid in the given block, split into the burned base fee and
+    /// the priority fees paid to the fee recipient.
+    ///
+    /// Returns `None` if the block does not exist.
+    pub async fn block_fee_breakdown(&self, block_id: BlockId) -> EthResult<Option<FeeBreakdown>> {
+        let Some(header) = self.provider().header_by_id(block_id)? else { return Ok(None) };
+        let Some(receipts) = self.block_receipts(block_id).await? else { return Ok(None) };
+        let base_fee = U256::from(header.base_fee_per_gas.unwrap_or_default());
+
+        let mut breakdown = FeeBreakdown::default();
+        for receipt in receipts {
+            let gas_used = receipt.gas_used.unwrap_or_default();
+            let gas_price = U256::from(receipt.effective_gas_price.to::<u128>());
+            breakdown.total_base_fee_burned += base_fee * gas_used;
+            breakdown.total_priority_fees += gas_price.saturating_sub(base_fee) * gas_used;
+            breakdown.total_fees += gas_price * gas_used;
+        }
+        Ok(Some(breakdown))
+    }
+
+    /// Returns the priority fee revenue of the given block and its difference to the priority fee
+    /// revenue of the previous block, see [Self::block_fee_breakdown].
+    ///
+    /// The difference is negative if the block earned less than the previous block.
+    ///
+    /// Returns `None` if the block does not exist or is the genesis block.
+    pub async fn block_revenue_delta(&self, block_id: BlockId) -> EthResult<Option<(U256, i128)>> {
+        let Some(header) = self.provider().header_by_id(block_id)? else { return Ok(None) };
+        let Some(parent_number) = header.number.checked_sub(1) else { return Ok(None) };
+
+        let (Some(current), Some(previous)) = futures::try_join!(
+            self.block_fee_breakdown(header.number.into()),
+            self.block_fee_breakdown(parent_number.into())
+        )?
+        else {
+            return Ok(None)
+        };
+
+        let revenue = current.total_priority_fees;
+        let delta =
+            revenue.saturating_to::<i128>() - previous.total_priority_fees.saturating_to::<i128>();
+        Ok(Some((revenue, delta)))
+    }
+
+    /// Returns the hash and gas used of every transaction in the given block, sorted in
+    /// descending order of gas used.
+    ///
+    /// Returns `None` if the block does not exist.
+    pub async fn block_transactions_by_gas_used(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<Vec<(B256, u64)>>> {
+        let Some(receipts) = self.block_receipts(block_id).await? else { return Ok(None) };
+        let mut gas_used = receipts
+            .iter()
+            .map(|receipt| {
+                (
+                    receipt.transaction_hash.unwrap_or_default(),
+                    receipt.gas_used.unwrap_or_default().to::<u64>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        gas_used.sort_by(|(_, a), (_, b)| b.cmp(a));
+        Ok(Some(gas_used))
+    }
+
+    /// Returns the ratio of gas used to gas limit of the last `block_count` blocks, ordered from
+    /// oldest to newest.
+    ///
+    /// Fewer ratios are returned if the chain has less than `block_count` blocks.
+    pub fn recent_gas_utilization(&self, block_count: u64) -> EthResult<Vec<f64>> {
+        if block_count == 0 {
+            return Ok(Vec::new())
+        }
+        let end = self.provider().best_block_number()?;
+        let start = end.saturating_sub(block_count - 1);
+        let ratios = self
+            .provider()
+            .headers_range(start..=end)?
+            .iter()
+            .map(|header| {
+                if header.gas_limit == 0 {
+                    0.0
+                } else {
+                    header.gas_used as f64 / header.gas_limit as f64
+                }
+            })
+            .collect();
+        Ok(ratios)
+    }
+
+    /// Returns the total number of logs emitted by all transactions in the given block.
+    ///
+    /// Returns `None` if the block does not exist.
+    pub async fn block_log_count(&self, block_id: BlockId) -> EthResult<Option<usize>> {
+        let Some(receipts) = self.block_receipts(block_id).await? else { return Ok(None) };
+        Ok(Some(receipts.iter().map(|receipt| receipt.logs.len()).sum()))
+    }
+
+    /// Returns `true` if any transaction in the given block failed.
+    ///
+    /// Returns `None` if the block does not exist.
+    pub async fn block_has_failed_transactions(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<bool>> {
+        let Some(receipts) = self.block_receipts(block_id).await? else { return Ok(None) };
+        Ok(Some(receipts.iter().any(|receipt| receipt.status_code.is_some_and(|s| s.is_zero()))))
+    }
+
+    /// Returns the hashes of all transactions in the given block that emitted a log with the given
+    /// event signature as its first topic, in block order.
+    pub async fn block_transactions_emitting(
+        &self,
+        block_id: BlockId,
+        event_sig: B256,
+    ) -> EthResult<Vec<B256>> {
+        let receipts =
+            self.block_receipts(block_id).await?.ok_or(EthApiError::UnknownBlockNumber)?;
+        Ok(receipts
+            .into_iter()
+            .filter(|receipt| receipt.logs.iter().any(|log| log.topics.first() == Some(&event_sig)))
+            .map(|receipt| receipt.transaction_hash.unwrap_or_default())
+            .collect())
+    }
+
+    /// Traces all transactions of the block and returns the hash of the creating transaction and
+    /// the address of every contract successfully deployed via `CREATE` or `CREATE2`, including
+    /// deployments by contracts.
+    ///
+    /// Returns `None` if the block does not exist.
+    pub async fn spawn_block_created_contracts(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<Vec<(B256, Address)>>> {
+        let created = self
+            .trace_block_with(
+                block_id,
+                TracingInspectorConfig::default_parity(),
+                |tx_info, inspector, _, _, _| {
+                    let hash = tx_info.hash.unwrap_or_default();
+                    Ok(inspector
+                        .get_traces()
+                        .nodes()
+                        .iter()
+                        .filter(|node| node.trace.kind.is_any_create() && node.trace.success)
+                        .map(|node| (hash, node.trace.address))
+                        .collect::<Vec<_>>())
+                },
+            )
+            .await?;
+        Ok(created.map(|created| created.into_iter().flatten().collect()))
+    }
+
+    /// Re-executes all transactions of the block and checks that the status, cumulative gas used
+    /// and logs of the resulting receipts match the stored receipts of the block.
+    ///
+    /// A mismatch is surfaced as `Some(false)`, this includes a different number of receipts than
+    /// transactions. Callers can then compare the stored receipts against a trace of the block to
+    /// find the diverging transaction.
+    ///
+    /// Returns `None` if the block or its receipts do not exist.
+    pub async fn spawn_verify_block_receipts(&self, block_id: BlockId) -> EthResult<Option<bool>> {
+        let Some(stored) = self.provider().receipts_by_block_id(block_id)? else { return Ok(None) };
+        let Some(executed) = self
+            .trace_block_until(
+                block_id,
+                None,
+                TracingInspectorConfig::default_parity(),
+                |_, _, res, _, _| {
+                    let logs = res.logs().into_iter().map(into_reth_log).collect::<Vec<_>>();
+                    Ok((res.is_success(), res.gas_used(), logs))
+                },
+            )
+            .await?
+        else {
+            return Ok(None)
+        };
+
+        if stored.len() != executed.len() {
+            return Ok(Some(false))
+        }
+
+        let mut cumulative_gas_used = 0;
+        let matches = stored.iter().zip(executed).all(|(receipt, (success, gas_used, logs))| {
+            cumulative_gas_used += gas_used;
+            receipt.success == success &&
+                receipt.cumulative_gas_used == cumulative_gas_used &&
+                receipt.logs == logs
+        });
+        Ok(Some(matches))
+    }
+
+    /// Re-executes all transactions of the block on top of the state of its parent and returns
+    /// the wall-clock time spent executing each transaction.
+    ///
+    /// The timings only cover the EVM execution, not the time spent to load the block and state.
+    ///
+    /// Returns `None` if the block does not exist.
+    pub async fn spawn_block_execution_timings(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<Vec<(B256, Duration)>>> {
+        self.spawn_replay_block(block_id, |db, env, tx| {
+            let started = Instant::now();
+            let (ResultAndState { state, .. }, _) = transact(&mut *db, env)?;
+            let elapsed = started.elapsed();
+
+            db.commit(state);
+            Ok((tx.hash(), elapsed))
+        })
+        .await
+    }
+
+    /// Re-executes all transactions of the block with the given base fee.
+    ///
+    /// Returns the execution result of every transaction, or the error if the transaction is not
+    /// executable with the new base fee, for example because its max fee per gas is lower than the
+    /// base fee. The state changes of failed transactions are skipped.
+    ///
+    /// Returns `None` if the block does not exist.
+    pub async fn spawn_block_with_base_fee_override(
+        &self,
+        block_id: BlockId,
+        base_fee: U256,
+    ) -> EthResult<Option<Vec<EthResult<ExecutionResult>>>> {
+        self.spawn_replay_block(block_id, move |db, mut env, _| {
+            env.block.basefee = base_fee;
+            Ok(transact(&mut *db, env).map(|(ResultAndState { result, state }, _)| {
+                db.commit(state);
+                result
+            }))
+        })
+        .await
+    }
+
+    /// Returns the hash of the most recent transaction that modified the given storage slot of the
+    /// contract.
+    ///
+    /// This re-executes the blocks from `up_to_block` backwards, scanning at most `scan_depth`
+    /// blocks. Blocks that don't exist are skipped.
+    ///
+    /// Returns `None` if no transaction in the scanned blocks modified the slot.
+    pub async fn spawn_last_writer_of_slot(
+        &self,
+        contract: Address,
+        slot: B256,
+        up_to_block: BlockNumber,
+        scan_depth: u64,
+    ) -> EthResult<Option<B256>> {
+        if scan_depth == 0 {
+            return Ok(None)
+        }
+        let slot = U256::from_be_bytes(slot.0);
+        let lowest = up_to_block.saturating_sub(scan_depth - 1);
+
+        for number in (lowest..=up_to_block).rev() {
+            let Some(block_hash) = self.provider().block_hash(number)? else { continue };
+            let writers = self
+                .trace_block_with(
+                    block_hash.into(),
+                    TracingInspectorConfig::default_parity(),
+                    move |tx_info, _, _, state, _| {
+                        let written = state
+                            .get(&contract)
+                            .and_then(|account| account.storage.get(&slot))
+                            .is_some_and(|value| value.is_changed());
+                        Ok(tx_info.hash.filter(|_| written))
+                    },
+                )
+                .await?;
+
+            if let Some(writer) = writers.into_iter().flatten().flatten().last() {
+                return Ok(Some(writer))
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// The transaction fees paid in a block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeeBreakdown {
+    /// Sum of the base fees burned by all transactions.
+    pub total_base_fee_burned: U256,
+    /// Sum of the priority fees paid to the fee recipient by all transactions.
+    pub total_priority_fees: U256,
+    /// Sum of all fees paid by all transactions.
+    pub total_fees: U256,
+}
+
+/// Statistics of the gas used by the transactions of a block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GasStats {
+    /// Lowest gas used by a transaction.
+    pub min: u64,
+    /// Highest gas used by a transaction.
+    pub max: u64,
+    /// Average gas used per transaction.
+    pub avg: u64,
+    /// Median gas used per transaction.
+    pub median: u64,
+    /// Total gas used by all transactions.
+    pub total: u64,
+}
+
+impl GasStats {
+    /// Computes the statistics from the gas used by each transaction, sorted in ascending order.
+    ///
+    /// All values are zero if there are no transactions.
+    fn from_sorted(gas_used: &[u64]) -> Self {
+        let (Some(&min), Some(&max)) = (gas_used.first(), gas_used.last()) else {
+            return Self::default()
+        };
+        let total = gas_used.iter().sum::<u64>();
+        let mid = gas_used.len() / 2;
+        let median = if gas_used.len() % 2 == 0 {
+            (gas_used[mid - 1] + gas_used[mid]) / 2
+        } else {
+            gas_used[mid]
+        };
+        Self { min, max, avg: total / gas_used.len() as u64, median, total }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FeeBreakdown, GasStats};
+    use crate::eth::{
+        api::test_utils::build_test_eth_api,
+        error::{EthApiError, RpcInvalidTransactionError},
+    };
+    use reth_interfaces::test_utils::generators::{
+        self, generate_keys, random_block, sign_tx_with_key_pair,
+    };
+    use reth_primitives::{
+        hex_literal::hex,
+        Address, Block, Bytes, Header, Log, Receipt, Transaction,
+        TransactionKind::{Call, Create},
+        TxEip1559, TxEip2930, TxLegacy, Withdrawal, B256, U256,
+    };
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    #[tokio::test]
+    async fn block_eth_burned() {
+        let provider = MockEthProvider::default();
+
+        let london_hash = B256::random();
+        let london_header = Header {
+            number: 1,
+            gas_used: 63_000,
+            base_fee_per_gas: Some(7_000_000_000),
+            ..Default::default()
+        };
+        provider.add_block(london_hash, Block { header: london_header, ..Default::default() });
+
+        let pre_london_hash = B256::random();
+        let pre_london_header = Header { number: 0, gas_used: 21_000, ..Default::default() };
+        provider
+            .add_block(pre_london_hash, Block { header: pre_london_header, ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let burned = eth_api.block_eth_burned(london_hash.into()).await.unwrap();
+        assert_eq!(burned, Some(U256::from(7_000_000_000u64 * 63_000)));
+
+        let burned = eth_api.block_eth_burned(pre_london_hash.into()).await.unwrap();
+        assert_eq!(burned, None);
+
+        let burned = eth_api.block_eth_burned(B256::random().into()).await.unwrap();
+        assert_eq!(burned, None);
+    }
+
+    #[tokio::test]
+    async fn block_gas_stats() {
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 1, None, Some(3), Some(0));
+        let receipts = block
+            .body
+            .iter()
+            .zip([21_000, 71_000, 101_000])
+            .map(|(tx, cumulative_gas_used)| Receipt {
+                tx_type: tx.tx_type(),
+                success: true,
+                cumulative_gas_used,
+                ..Default::default()
+            })
+            .collect();
+
+        let provider = MockEthProvider::default();
+        provider.add_block(block.hash, block.clone().unseal());
+        provider.add_receipts(block.hash, receipts);
+
+        let eth_api = build_test_eth_api(provider);
+
+        let stats = eth_api.block_gas_stats(block.hash.into()).await.unwrap().unwrap();
+        assert_eq!(
+            stats,
+            GasStats { min: 21_000, max: 50_000, avg: 33_666, median: 30_000, total: 101_000 }
+        );
+
+        assert!(eth_api.block_gas_stats(B256::random().into()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn block_effective_gas_prices() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+        let body = [
+            Transaction::Legacy(TxLegacy { gas_price: 15, ..Default::default() }),
+            Transaction::Eip1559(TxEip1559 {
+                max_fee_per_gas: 30,
+                max_priority_fee_per_gas: 12,
+                ..Default::default()
+            }),
+            Transaction::Eip1559(TxEip1559 {
+                max_fee_per_gas: 12,
+                max_priority_fee_per_gas: 5,
+                ..Default::default()
+            }),
+            Transaction::Eip2930(TxEip2930 { gas_price: 20, ..Default::default() }),
+        ]
+        .into_iter()
+        .map(|tx| sign_tx_with_key_pair(key_pair, tx))
+        .collect();
+
+        let provider = MockEthProvider::default();
+        let block_hash = B256::random();
+        provider.add_block(
+            block_hash,
+            Block {
+                header: Header { base_fee_per_gas: Some(10), ..Default::default() },
+                body,
+                ..Default::default()
+            },
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        let prices = eth_api.block_effective_gas_prices(block_hash.into()).await.unwrap();
+        assert_eq!(
+            prices,
+            Some(vec![U256::from(22), U256::from(20), U256::from(15), U256::from(12)])
+        );
+
+        assert!(eth_api.block_effective_gas_prices(B256::random().into()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn block_median_gas_price() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+        let sign = |gas_prices: &[u128]| {
+            gas_prices
+                .iter()
+                .map(|&gas_price| {
+                    sign_tx_with_key_pair(
+                        key_pair,
+                        Transaction::Legacy(TxLegacy { gas_price, ..Default::default() }),
+                    )
+                })
+                .collect()
+        };
+
+        let provider = MockEthProvider::default();
+        let (odd, even, empty) = (B256::random(), B256::random(), B256::random());
+        provider.add_block(odd, Block { body: sign(&[30, 10, 20]), ..Default::default() });
+        provider.add_block(even, Block { body: sign(&[40, 10, 15, 20]), ..Default::default() });
+        provider.add_block(empty, Block::default());
+
+        let eth_api = build_test_eth_api(provider);
+
+        assert_eq!(eth_api.block_median_gas_price(odd.into()).await.unwrap(), Some(U256::from(20)));
+        // mean of 15 and 20, rounded down
+        assert_eq!(
+            eth_api.block_median_gas_price(even.into()).await.unwrap(),
+            Some(U256::from(17))
+        );
+        assert!(eth_api.block_median_gas_price(empty.into()).await.unwrap().is_none());
+        assert!(eth_api.block_median_gas_price(B256::random().into()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn block_fee_breakdown() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+        let body = [
+            Transaction::Legacy(TxLegacy { gas_price: 15, ..Default::default() }),
+            Transaction::Eip1559(TxEip1559 {
+                max_fee_per_gas: 30,
+                max_priority_fee_per_gas: 12,
+                ..Default::default()
+            }),
+        ]
+        .into_iter()
+        .map(|tx| sign_tx_with_key_pair(key_pair, tx))
+        .collect::<Vec<_>>();
+        let receipts = body
+            .iter()
+            .zip([21_000, 42_000])
+            .map(|(tx, cumulative_gas_used)| Receipt {
+                tx_type: tx.tx_type(),
+                success: true,
+                cumulative_gas_used,
+                ..Default::default()
+            })
+            .collect();
+
+        let provider = MockEthProvider::default();
+        let block_hash = B256::random();
+        provider.add_block(
+            block_hash,
+            Block {
+                header: Header {
+                    gas_used: 42_000,
+                    base_fee_per_gas: Some(10),
+                    ..Default::default()
+                },
+                body,
+                ..Default::default()
+            },
+        );
+        provider.add_receipts(block_hash, receipts);
+
+        let eth_api = build_test_eth_api(provider);
+
+        let breakdown = eth_api.block_fee_breakdown(block_hash.into()).await.unwrap().unwrap();
+        assert_eq!(
+            breakdown,
+            FeeBreakdown {
+                total_base_fee_burned: U256::from(42_000 * 10),
+                total_priority_fees: U256::from(21_000 * 5 + 21_000 * 12),
+                total_fees: U256::from(21_000 * 15 + 21_000 * 22),
+            }
+        );
+        assert_eq!(
+            breakdown.total_base_fee_burned + breakdown.total_priority_fees,
+            breakdown.total_fees
+        );
+        assert_eq!(
+            Some(breakdown.total_base_fee_burned),
+            eth_api.block_eth_burned(block_hash.into()).await.unwrap()
+        );
+
+        assert!(eth_api.block_fee_breakdown(B256::random().into()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn block_withdrawal_total() {
+        let provider = MockEthProvider::default();
+
+        let shanghai_hash = B256::random();
+        let withdrawals = vec![
+            Withdrawal { index: 0, validator_index: 1, address: Default::default(), amount: 32 },
+            Withdrawal { index: 1, validator_index: 2, address: Default::default(), amount: 5 },
+        ];
+        provider.add_block(
+            shanghai_hash,
+            Block { withdrawals: Some(withdrawals), ..Default::default() },
+        );
+
+        let pre_shanghai_hash = B256::random();
+        provider.add_block(pre_shanghai_hash, Default::default());
+
+        let eth_api = build_test_eth_api(provider);
+
+        let total = eth_api.block_withdrawal_total(shanghai_hash.into()).await.unwrap();
+        assert_eq!(total, Some(U256::from(37_000_000_000u64)));
+
+        let total = eth_api.block_withdrawal_total(pre_shanghai_hash.into()).await.unwrap();
+        assert_eq!(total, Some(U256::ZERO));
+
+        assert!(eth_api.block_withdrawal_total(B256::random().into()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn block_has_failed_transactions() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+        let provider = MockEthProvider::default();
+
+        let add_block = |outcomes: &[bool]| {
+            let body = outcomes
+                .iter()
+                .map(|_| sign_tx_with_key_pair(key_pair, Transaction::Legacy(Default::default())))
+                .collect::<Vec<_>>();
+            let receipts =
+                outcomes.iter().map(|&success| Receipt { success, ..Default::default() }).collect();
+            let block_hash = B256::random();
+            provider.add_block(block_hash, Block { body, ..Default::default() });
+            provider.add_receipts(block_hash, receipts);
+            block_hash
+        };
+        let failed = add_block(&[true, false, true]);
+        let succeeded = add_block(&[true, true]);
+
+        let eth_api = build_test_eth_api(provider);
+
+        assert_eq!(eth_api.block_has_failed_transactions(failed.into()).await.unwrap(), Some(true));
+        assert_eq!(
+            eth_api.block_has_failed_transactions(succeeded.into()).await.unwrap(),
+            Some(false)
+        );
+        assert!(eth_api
+            .block_has_failed_transactions(B256::random().into())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn block_transactions_by_gas_used() {
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 1, None, Some(3), Some(0));
+        let receipts = block
+            .body
+            .iter()
+            .zip([21_000, 71_000, 101_000])
+            .map(|(tx, cumulative_gas_used)| Receipt {
+                tx_type: tx.tx_type(),
+                success: true,
+                cumulative_gas_used,
+                ..Default::default()
+            })
+            .collect();
+
+        let provider = MockEthProvider::default();
+        provider.add_block(block.hash, block.clone().unseal());
+        provider.add_receipts(block.hash, receipts);
+
+        let eth_api = build_test_eth_api(provider);
+
+        let sorted = eth_api.block_transactions_by_gas_used(block.hash.into()).await.unwrap();
+        assert_eq!(
+            sorted,
+            Some(vec![
+                (block.body[1].hash, 50_000),
+                (block.body[2].hash, 30_000),
+                (block.body[0].hash, 21_000),
+            ])
+        );
+
+        assert!(eth_api
+            .block_transactions_by_gas_used(B256::random().into())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn recent_gas_utilization() {
+        let provider = MockEthProvider::default();
+        for (number, gas_used) in [(0, 30_000_000), (1, 15_000_000), (2, 0), (3, 7_500_000)] {
+            provider.add_header(
+                B256::random(),
+                Header { number, gas_used, gas_limit: 30_000_000, ..Default::default() },
+            );
+        }
+
+        let eth_api = build_test_eth_api(provider);
+
+        assert_eq!(eth_api.recent_gas_utilization(3).unwrap(), vec![0.5, 0.0, 0.25]);
+        assert_eq!(eth_api.recent_gas_utilization(10).unwrap(), vec![1.0, 0.5, 0.0, 0.25]);
+        assert!(eth_api.recent_gas_utilization(0).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn block_log_count() {
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 1, None, Some(3), Some(0));
+        let log_counts = [2, 0, 3];
+        let receipts = block
+            .body
+            .iter()
+            .zip(log_counts)
+            .map(|(tx, logs)| Receipt {
+                tx_type: tx.tx_type(),
+                success: true,
+                logs: vec![Default::default(); logs],
+                ..Default::default()
+            })
+            .collect();
+
+        let provider = MockEthProvider::default();
+        provider.add_block(block.hash, block.clone().unseal());
+        provider.add_receipts(block.hash, receipts);
+
+        let eth_api = build_test_eth_api(provider);
+
+        let count = eth_api.block_log_count(block.hash.into()).await.unwrap();
+        assert_eq!(count, Some(log_counts.iter().sum()));
+
+        assert!(eth_api.block_log_count(B256::random().into()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn block_transactions_emitting() {
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 1, None, Some(3), Some(0));
+        let event_sig = B256::random();
+        let log = |topic| Log {
+            address: Address::random(),
+            topics: vec![topic],
+            data: Default::default(),
+        };
+        let receipts = block
+            .body
+            .iter()
+            .zip([
+                vec![log(event_sig)],
+                vec![log(B256::random())],
+                vec![Log::default(), log(event_sig)],
+            ])
+            .map(|(tx, logs)| Receipt {
+                tx_type: tx.tx_type(),
+                success: true,
+                logs,
+                ..Default::default()
+            })
+            .collect();
+
+        let provider = MockEthProvider::default();
+        provider.add_block(block.hash, block.clone().unseal());
+        provider.add_receipts(block.hash, receipts);
+
+        let eth_api = build_test_eth_api(provider);
+
+        let hashes =
+            eth_api.block_transactions_emitting(block.hash.into(), event_sig).await.unwrap();
+        assert_eq!(hashes, vec![block.body[0].hash, block.body[2].hash]);
+
+        assert!(eth_api
+            .block_transactions_emitting(B256::random().into(), event_sig)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn block_revenue_delta() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+        let provider = MockEthProvider::default();
+
+        // one legacy transaction per block, paying the given priority fee on a base fee of 10
+        let mut hashes = Vec::new();
+        for (number, gas_price) in [(0, 11), (1, 15), (2, 12)] {
+            let tx = sign_tx_with_key_pair(
+                key_pair,
+                Transaction::Legacy(TxLegacy { nonce: number, gas_price, ..Default::default() }),
+            );
+            let block_hash = B256::random();
+            provider.add_block(
+                block_hash,
+                Block {
+                    header: Header {
+                        number,
+                        gas_used: 21_000,
+                        base_fee_per_gas: Some(10),
+                        ..Default::default()
+                    },
+                    body: vec![tx],
+                    ..Default::default()
+                },
+            );
+            provider.add_receipts(
+                block_hash,
+                vec![Receipt { success: true, cumulative_gas_used: 21_000, ..Default::default() }],
+            );
+            hashes.push(block_hash);
+        }
+
+        let eth_api = build_test_eth_api(provider);
+
+        assert_eq!(
+            eth_api.block_revenue_delta(hashes[1].into()).await.unwrap(),
+            Some((U256::from(21_000 * 5), 21_000 * 4))
+        );
+        assert_eq!(
+            eth_api.block_revenue_delta(hashes[2].into()).await.unwrap(),
+            Some((U256::from(21_000 * 2), -21_000 * 3))
+        );
+        // the genesis block has no previous block
+        assert!(eth_api.block_revenue_delta(hashes[0].into()).await.unwrap().is_none());
+        assert!(eth_api.block_revenue_delta(B256::random().into()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn block_created_contracts() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // deploys a contract with empty code
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Create,
+                input: Bytes::from_static(&hex!("00")),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+        let created = tx.recover_signer().unwrap().create(0);
+
+        let provider = MockEthProvider::default();
+        let block_hash = B256::random();
+        provider.add_block(block_hash, Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let contracts =
+            eth_api.spawn_block_created_contracts(block_hash.into()).await.unwrap().unwrap();
+        assert_eq!(contracts, vec![(hash, created)]);
+
+        assert!(eth_api
+            .spawn_block_created_contracts(B256::random().into())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn verify_block_receipts() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // emits an empty log: PUSH1 0 PUSH1 0 LOG0 STOP
+        let logging = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            logging,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("60006000a000"))),
+        );
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(logging),
+                ..Default::default()
+            }),
+        );
+        let receipt = Receipt {
+            success: true,
+            cumulative_gas_used: 21_000 + 3 + 3 + 375,
+            logs: vec![Log { address: logging, topics: vec![], data: Default::default() }],
+            ..Default::default()
+        };
+
+        let valid = B256::random();
+        provider.add_block(valid, Block { body: vec![tx.clone()], ..Default::default() });
+        provider.add_receipts(valid, vec![receipt.clone()]);
+
+        let tampered = B256::random();
+        provider.add_block(tampered, Block { body: vec![tx], ..Default::default() });
+        provider.add_receipts(tampered, vec![Receipt { logs: vec![], ..receipt }]);
+
+        let eth_api = build_test_eth_api(provider);
+
+        assert_eq!(eth_api.spawn_verify_block_receipts(valid.into()).await.unwrap(), Some(true));
+        assert_eq!(
+            eth_api.spawn_verify_block_receipts(tampered.into()).await.unwrap(),
+            Some(false)
+        );
+        assert!(eth_api
+            .spawn_verify_block_receipts(B256::random().into())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn block_execution_timings() {
+        let mut rng = generators::rng();
+        let body = generate_keys(&mut rng, 3)
+            .into_iter()
+            .map(|key_pair| {
+                sign_tx_with_key_pair(
+                    key_pair,
+                    Transaction::Legacy(TxLegacy {
+                        chain_id: Some(1),
+                        gas_limit: 21_000,
+                        to: Call(Address::random()),
+                        ..Default::default()
+                    }),
+                )
+            })
+            .collect::<Vec<_>>();
+        let hashes = body.iter().map(|tx| tx.hash).collect::<Vec<_>>();
+
+        let provider = MockEthProvider::default();
+        let block_hash = B256::random();
+        provider.add_block(block_hash, Block { body, ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let timings =
+            eth_api.spawn_block_execution_timings(block_hash.into()).await.unwrap().unwrap();
+        assert_eq!(timings.iter().map(|(hash, _)| *hash).collect::<Vec<_>>(), hashes);
+
+        assert!(eth_api
+            .spawn_block_execution_timings(B256::random().into())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn block_with_base_fee_override() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+
+        let high_fee = sign_tx_with_key_pair(
+            keys[0],
+            Transaction::Eip1559(TxEip1559 {
+                chain_id: 1,
+                gas_limit: 21_000,
+                max_fee_per_gas: 100,
+                to: Call(Address::random()),
+                ..Default::default()
+            }),
+        );
+        let low_fee = sign_tx_with_key_pair(
+            keys[1],
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_price: 10,
+                gas_limit: 21_000,
+                to: Call(Address::random()),
+                ..Default::default()
+            }),
+        );
+
+        let provider = MockEthProvider::default();
+        for tx in [&high_fee, &low_fee] {
+            provider.add_account(
+                tx.recover_signer().unwrap(),
+                ExtendedAccount::new(0, U256::from(10_000_000)),
+            );
+        }
+        let block_hash = B256::random();
+        provider
+            .add_block(block_hash, Block { body: vec![high_fee, low_fee], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let results = eth_api
+            .spawn_block_with_base_fee_override(block_hash.into(), U256::from(50))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].as_ref().unwrap().is_success());
+        assert!(matches!(
+            results[1],
+            Err(EthApiError::InvalidTransaction(RpcInvalidTransactionError::FeeCapTooLow))
+        ));
+
+        // both transactions pay at least a base fee of 10
+        let results = eth_api
+            .spawn_block_with_base_fee_override(block_hash.into(), U256::from(10))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(results.iter().all(|result| result.as_ref().is_ok_and(|res| res.is_success())));
+
+        assert!(eth_api
+            .spawn_block_with_base_fee_override(B256::random().into(), U256::from(50))
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn last_writer_of_slot() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 3);
+
+        // PUSH1 42 PUSH1 1 SSTORE STOP
+        let storing = Address::random();
+        let sign = |key_pair, to| {
+            sign_tx_with_key_pair(
+                key_pair,
+                Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_limit: 100_000,
+                    to: Call(to),
+                    ..Default::default()
+                }),
+            )
+        };
+        let writer = sign(keys[0], storing);
+        let writer_hash = writer.hash;
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            storing,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("602a60015500"))),
+        );
+        let blocks = [
+            vec![writer, sign(keys[1], Address::random())],
+            vec![],
+            vec![sign(keys[2], Address::random())],
+        ];
+        for (number, body) in blocks.into_iter().enumerate() {
+            provider.add_block(
+                B256::random(),
+                Block {
+                    header: Header { number: number as u64 + 1, ..Default::default() },
+                    body,
+                    ..Default::default()
+                },
+            );
+        }
+
+        let eth_api = build_test_eth_api(provider);
+
+        let slot = B256::with_last_byte(1);
+        assert_eq!(
+            eth_api.spawn_last_writer_of_slot(storing, slot, 3, 5).await.unwrap(),
+            Some(writer_hash)
+        );
+        // the write is out of the scanned range
+        assert_eq!(eth_api.spawn_last_writer_of_slot(storing, slot, 3, 2).await.unwrap(), None);
+        // the slot was never written
+        assert_eq!(
+            eth_api.spawn_last_writer_of_slot(storing, B256::ZERO, 3, 5).await.unwrap(),
+            None
+        );
+    }
 }
@@ -2,29 +2,34 @@
 
 use crate::{
     eth::{
-        error::{ensure_success, EthApiError, EthResult, RevertError, RpcInvalidTransactionError},
+        api::pending_block::PendingBlockEnv,
+        error::{ensure_success, EthApiError, EthResult, RpcInvalidTransactionError},
         revm_utils::{
-            apply_state_overrides, build_call_evm_env, caller_gas_allowance,
+            apply_block_overrides, apply_state_overrides, build_call_evm_env, caller_gas_allowance,
             cap_tx_gas_limit_with_caller_allowance, get_precompiles, inspect, prepare_call_env,
             transact, EvmOverrides,
         },
+        utils::recover_raw_transaction,
         EthTransactions,
     },
     EthApi,
 };
 use reth_network_api::NetworkInfo;
-use reth_primitives::{revm::env::tx_env_with_recovered, BlockId, BlockNumberOrTag, Bytes, U256};
+use reth_primitives::{
+    revm::env::tx_env_with_recovered, Address, BlockId, BlockNumberOrTag, Bytes, B256, U256,
+};
 use reth_provider::{
     BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProvider, StateProviderFactory,
 };
 use reth_revm::{access_list::AccessListInspector, database::StateProviderDatabase};
 use reth_rpc_types::{
-    state::StateOverride, AccessListWithGasUsed, Bundle, CallRequest, EthCallResponse, StateContext,
+    state::StateOverride, AccessListWithGasUsed, BlockOverrides, Bundle, CallRequest,
+    EthCallResponse, Log, StateContext,
 };
 use reth_transaction_pool::TransactionPool;
 use revm::{
     db::{CacheDB, DatabaseRef},
-    primitives::{BlockEnv, CfgEnv, Env, ExecutionResult, Halt, TransactTo},
+    primitives::{BlockEnv, CfgEnv, Env, ExecutionResult, Halt, ResultAndState, TransactTo},
     DatabaseCommit,
 };
 use tracing::trace;
@@ -56,6 +61,23 @@ where
         .await
     }
 
+    /// Estimate gas needed for execution of the `request` as if it were the first transaction of
+    /// the next block.
+    ///
+    /// Unlike [Self::estimate_gas_at] with the pending tag, this executes against the state the
+    /// pending block is built on, so it's not affected by the transactions already in the pending
+    /// block.
+    pub async fn estimate_gas_top_of_block(&self, request: CallRequest) -> EthResult<U256> {
+        let PendingBlockEnv { cfg, block_env, origin } = self.pending_block_env_and_cfg()?;
+        let at = origin.build_target_hash().into();
+
+        self.on_blocking_task(|this| async move {
+            let state = this.state_at(at)?;
+            this.estimate_gas_with(cfg, block_env, request, state, None)
+        })
+        .await
+    }
+
     /// Executes the call request (`eth_call`) and returns the output
     pub async fn call(
         &self,
@@ -166,6 +188,188 @@ where
         .await
     }
 
+    /// Simulates a sequence of blocks on top of the state at the given base [BlockId].
+    ///
+    /// Every block is built on top of the previous one: its number is incremented by one and its
+    /// timestamp by [SIMULATED_BLOCK_TIME], before the block's [BlockOverrides] are applied. The
+    /// calls of a block are executed in order and their state changes are committed, so later
+    /// calls and blocks observe the effects of earlier ones.
+    ///
+    /// A call that is invalid, reverts or halts doesn't abort the simulation, its error is
+    /// reported in the [SimulatedCall] instead.
+    pub async fn simulate_blocks(
+        &self,
+        base: BlockId,
+        block_state_calls: Vec<(BlockOverrides, Vec<CallRequest>)>,
+    ) -> EthResult<Vec<SimulatedBlock>> {
+        if block_state_calls.is_empty() {
+            return Err(EthApiError::InvalidParams(String::from("blocks are empty.")))
+        }
+
+        let (cfg, mut block_env, at) = self.evm_env_at(base).await?;
+        let gas_limit = self.inner.gas_cap;
+
+        self.spawn_with_state_at_block(at, move |state| {
+            let mut db = CacheDB::new(StateProviderDatabase::new(state));
+            let mut blocks = Vec::with_capacity(block_state_calls.len());
+
+            for (block_overrides, calls) in block_state_calls {
+                let parent_number = block_env.number;
+                let parent_timestamp = block_env.timestamp;
+                block_env.number += U256::from(1);
+                block_env.timestamp += U256::from(SIMULATED_BLOCK_TIME);
+                apply_block_overrides(block_overrides, &mut block_env);
+
+                if block_env.number <= parent_number || block_env.timestamp <= parent_timestamp {
+                    return Err(EthApiError::InvalidParams(String::from(
+                        "block number and timestamp must be increasing.",
+                    )))
+                }
+
+                let mut block = SimulatedBlock {
+                    number: block_env.number,
+                    timestamp: block_env.timestamp,
+                    base_fee_per_gas: block_env.basefee,
+                    coinbase: block_env.coinbase,
+                    gas_used: 0,
+                    calls: Vec::with_capacity(calls.len()),
+                };
+
+                let mut log_index = 0;
+                for (index, call) in calls.into_iter().enumerate() {
+                    let env = prepare_call_env(
+                        cfg.clone(),
+                        block_env.clone(),
+                        call,
+                        gas_limit,
+                        &mut db,
+                        EvmOverrides::default(),
+                    )?;
+
+                    let outcome = match transact(&mut db, env) {
+                        Ok((ResultAndState { result, state }, _)) => {
+                            db.commit(state);
+                            let gas_used = result.gas_used();
+                            let logs = result
+                                .logs()
+                                .into_iter()
+                                .map(|log| {
+                                    let log = Log {
+                                        address: log.address,
+                                        topics: log.topics,
+                                        data: log.data,
+                                        block_hash: None,
+                                        block_number: Some(block.number),
+                                        transaction_hash: None,
+                                        transaction_index: Some(U256::from(index)),
+                                        log_index: Some(U256::from(log_index)),
+                                        removed: false,
+                                    };
+                                    log_index += 1;
+                                    log
+                                })
+                                .collect();
+                            let (output, error) = match ensure_success(result) {
+                                Ok(output) => (Some(output), None),
+                                Err(err) => (None, Some(err.to_string())),
+                            };
+                            SimulatedCall { gas_used, output, error, logs }
+                        }
+                        Err(err) => SimulatedCall {
+                            gas_used: 0,
+                            output: None,
+                            error: Some(err.to_string()),
+                            logs: Vec::new(),
+                        },
+                    };
+                    block.gas_used += outcome.gas_used;
+                    block.calls.push(outcome);
+                }
+
+                blocks.push(block);
+            }
+
+            Ok(blocks)
+        })
+        .await
+    }
+
+    /// Executes the given raw transactions in every possible order on top of the state at the
+    /// given [BlockId].
+    ///
+    /// Returns the outcomes of the transactions for each ordering, in execution order. The number
+    /// of transactions is limited to [MAX_ORDERING_TRANSACTIONS] to bound the number of orderings.
+    pub async fn simulate_ordering(
+        &self,
+        txs: Vec<Bytes>,
+        at: BlockId,
+    ) -> EthResult<Vec<Vec<CallOutcome>>> {
+        if txs.is_empty() {
+            return Err(EthApiError::InvalidParams(String::from("transactions are empty.")))
+        }
+        if txs.len() > MAX_ORDERING_TRANSACTIONS {
+            return Err(EthApiError::InvalidParams(format!(
+                "too many transactions to order, maximum is {MAX_ORDERING_TRANSACTIONS}"
+            )))
+        }
+
+        let transactions = txs
+            .into_iter()
+            .map(|tx| recover_raw_transaction(tx).map(|tx| tx.into_ecrecovered_transaction()))
+            .collect::<EthResult<Vec<_>>>()?;
+
+        let (cfg, block_env, at) = self.evm_env_at(at).await?;
+
+        self.spawn_with_state_at_block(at, move |state| {
+            let mut orderings = Vec::new();
+            for ordering in permutations(transactions.len()) {
+                let mut db = CacheDB::new(StateProviderDatabase::new(&state));
+                let mut outcomes = Vec::with_capacity(ordering.len());
+
+                for index in ordering {
+                    let tx = &transactions[index];
+                    let env = Env {
+                        cfg: cfg.clone(),
+                        block: block_env.clone(),
+                        tx: tx_env_with_recovered(tx),
+                    };
+
+                    let outcome = match transact(&mut db, env) {
+                        Ok((ResultAndState { result, state }, _)) => {
+                            db.commit(state);
+                            let gas_used = result.gas_used();
+                            match ensure_success(result) {
+                                Ok(output) => CallOutcome {
+                                    hash: tx.hash,
+                                    gas_used,
+                                    output: Some(output),
+                                    error: None,
+                                },
+                                Err(err) => CallOutcome {
+                                    hash: tx.hash,
+                                    gas_used,
+                                    output: None,
+                                    error: Some(err.to_string()),
+                                },
+                            }
+                        }
+                        Err(err) => CallOutcome {
+                            hash: tx.hash,
+                            gas_used: 0,
+                            output: None,
+                            error: Some(err.to_string()),
+                        },
+                    };
+                    outcomes.push(outcome);
+                }
+
+                orderings.push(outcomes);
+            }
+            Ok(orderings)
+        })
+        .await
+    }
+
     /// Estimates the gas usage of the `request` with the state.
     ///
     /// This will execute the [CallRequest] and find the best gas limit via binary search
@@ -273,7 +477,7 @@ where
                     Err(map_out_of_gas_err(env_gas_limit, env, &mut db))
                 } else {
                     // the transaction did revert
-                    Err(RpcInvalidTransactionError::Revert(RevertError::new(output)).into())
+                    Err(EthApiError::reverted(output))
                 }
             }
         }
@@ -356,7 +560,26 @@ where
         block_number: Option<BlockId>,
     ) -> EthResult<AccessListWithGasUsed> {
         self.on_blocking_task(|this| async move {
-            this.create_access_list_with(request, block_number).await
+            this.create_access_list_with(request, block_number, None).await
+        })
+        .await
+    }
+
+    /// Estimate gas needed for execution of the `request` at the [BlockId] with the access list
+    /// the request would benefit from.
+    ///
+    /// The access list is created like in [Self::create_access_list_at], including the state
+    /// overrides, and the gas is estimated with the created access list in place of the request's
+    /// access list, on the same state. Returns the estimated gas together with the created access
+    /// list.
+    pub async fn estimate_gas_with_access_list_at(
+        &self,
+        request: CallRequest,
+        at: BlockId,
+        state_override: Option<StateOverride>,
+    ) -> EthResult<AccessListWithGasUsed> {
+        self.on_blocking_task(|this| async move {
+            this.create_access_list_with(request, Some(at), state_override).await
         })
         .await
     }
@@ -365,6 +588,7 @@ where
         &self,
         mut request: CallRequest,
         at: Option<BlockId>,
+        state_override: Option<StateOverride>,
     ) -> EthResult<AccessListWithGasUsed> {
         let block_id = at.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
         let (cfg, block, at) = self.evm_env_at(block_id).await?;
@@ -383,6 +607,10 @@ where
 
         let mut db = CacheDB::new(StateProviderDatabase::new(state));
 
+        if let Some(state_override) = state_override.clone() {
+            apply_state_overrides(state_override, &mut db)?;
+        }
+
         if request.gas.is_none() && env.tx.gas_price > U256::ZERO {
             // no gas limit was provided in the request, so we need to cap the request's gas limit
             cap_tx_gas_limit_with_caller_allowance(&mut db, &mut env.tx)?;
@@ -397,31 +625,114 @@ where
         };
 
         // can consume the list since we're not using the request anymore
-        let initial = request.access_list.take().unwrap_or_default();
+        let mut access_list = request.access_list.take().unwrap_or_default();
+        let mut excluded_precompiles = Vec::new();
 
-        let precompiles = get_precompiles(env.cfg.spec_id);
-        let mut inspector = AccessListInspector::new(initial, from, to, precompiles);
-        let (result, env) = inspect(&mut db, env, &mut inspector)?;
+        // applying the access list changes the gas costs, which can change the execution path, for
+        // example if a call forwards a gas dependent amount, so we repeat until it is stable
+        for _ in 0..MAX_ACCESS_LIST_PASSES {
+            env.tx.access_list = access_list.flattened();
+            let precompiles = get_precompiles(env.cfg.spec_id);
+            let mut inspector =
+                AccessListInspector::new(access_list.clone(), from, to, precompiles);
+            let (result, pass_env) = inspect(&mut db, env, &mut inspector)?;
+            env = pass_env;
 
-        match result.result {
-            ExecutionResult::Halt { reason, .. } => Err(match reason {
-                Halt::NonceOverflow => RpcInvalidTransactionError::NonceMaxValue,
-                halt => RpcInvalidTransactionError::EvmHalt(halt),
-            }),
-            ExecutionResult::Revert { output, .. } => {
-                Err(RpcInvalidTransactionError::Revert(RevertError::new(output)))
-            }
-            ExecutionResult::Success { .. } => Ok(()),
-        }?;
+            match result.result {
+                ExecutionResult::Halt { reason, .. } => Err(match reason {
+                    Halt::NonceOverflow => RpcInvalidTransactionError::NonceMaxValue,
+                    halt => RpcInvalidTransactionError::EvmHalt(halt),
+                }
+                .into()),
+                ExecutionResult::Revert { output, .. } => Err(EthApiError::reverted(output)),
+                ExecutionResult::Success { .. } => Ok(()),
+            }?;
 
-        let access_list = inspector.into_access_list();
+            excluded_precompiles = inspector.accessed_precompiles();
+            let mut next = inspector.into_access_list();
+            next.0.sort_unstable_by_key(|item| item.address);
+            if next == access_list {
+                break
+            }
+            access_list = next;
+        }
 
         // calculate the gas used using the access list
         request.access_list = Some(access_list.clone());
-        let gas_used = self.estimate_gas_with(env.cfg, env.block, request, db.db.state(), None)?;
+        let gas_used =
+            self.estimate_gas_with(env.cfg, env.block, request, db.db.state(), state_override)?;
+
+        Ok(AccessListWithGasUsed { access_list, gas_used, excluded_precompiles })
+    }
+}
+
+/// The number of seconds a simulated block advances the timestamp by in
+/// [EthApi::simulate_blocks], unless the timestamp is overridden.
+pub const SIMULATED_BLOCK_TIME: u64 = 12;
+
+/// A block simulated by [EthApi::simulate_blocks].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedBlock {
+    /// Number of the block.
+    pub number: U256,
+    /// Timestamp of the block.
+    pub timestamp: U256,
+    /// Base fee of the block.
+    pub base_fee_per_gas: U256,
+    /// Beneficiary of the block.
+    pub coinbase: Address,
+    /// Total gas used by the calls of the block.
+    pub gas_used: u64,
+    /// Outcomes of the calls of the block, in execution order.
+    pub calls: Vec<SimulatedCall>,
+}
 
-        Ok(AccessListWithGasUsed { access_list, gas_used })
+/// The outcome of a call executed in a block simulated by [EthApi::simulate_blocks].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedCall {
+    /// Gas used by the call, zero if the call was invalid.
+    pub gas_used: u64,
+    /// Output of the call if it succeeded.
+    pub output: Option<Bytes>,
+    /// Error message if the call was invalid, reverted or halted.
+    pub error: Option<String>,
+    /// Logs emitted by the call.
+    pub logs: Vec<Log>,
+}
+
+/// The maximum number of executions used to find a stable access list in `eth_createAccessList`.
+const MAX_ACCESS_LIST_PASSES: usize = 3;
+
+/// The maximum number of transactions accepted by [EthApi::simulate_ordering].
+pub const MAX_ORDERING_TRANSACTIONS: usize = 4;
+
+/// The outcome of a transaction executed in one of the orderings of [EthApi::simulate_ordering].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallOutcome {
+    /// Hash of the transaction.
+    pub hash: B256,
+    /// Gas used by the transaction, zero if the transaction was invalid.
+    pub gas_used: u64,
+    /// Output of the transaction if it succeeded.
+    pub output: Option<Bytes>,
+    /// Error message if the transaction was invalid, reverted or halted.
+    pub error: Option<String>,
+}
+
+/// Returns all permutations of the indices `0..n`.
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![vec![]]
+    }
+    let mut result = Vec::new();
+    for permutation in permutations(n - 1) {
+        for position in 0..=permutation.len() {
+            let mut permutation = permutation.clone();
+            permutation.insert(position, n - 1);
+            result.push(permutation);
+        }
     }
+    result
 }
 
 /// Executes the requests again after an out of gas error to check if the error is gas related or
@@ -449,8 +760,311 @@ where
         }
         ExecutionResult::Revert { output, .. } => {
             // reverted again after bumping the limit
-            RpcInvalidTransactionError::Revert(RevertError::new(output)).into()
+            EthApiError::reverted(output)
         }
         ExecutionResult::Halt { reason, .. } => RpcInvalidTransactionError::EvmHalt(reason).into(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::api::test_utils::build_test_eth_api;
+    use reth_interfaces::test_utils::generators::{self, generate_keys, sign_tx_with_key_pair};
+    use reth_primitives::{hex_literal::hex, Address, Transaction, TransactionKind, TxLegacy, U64};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_rpc_types::{state::AccountOverride, AccessList, AccessListItem, CallInput};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_permutations() {
+        assert_eq!(permutations(1), vec![vec![0]]);
+        assert_eq!(permutations(2), vec![vec![1, 0], vec![0, 1]]);
+        assert_eq!(permutations(MAX_ORDERING_TRANSACTIONS).len(), 24);
+    }
+
+    #[tokio::test]
+    async fn test_simulate_ordering() {
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Default::default());
+        let eth_api = build_test_eth_api(provider);
+
+        let mut rng = generators::rng();
+        let txs = generate_keys(&mut rng, 2)
+            .into_iter()
+            .map(|key_pair| {
+                let tx = Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_limit: 21_000,
+                    to: TransactionKind::Call(Address::random()),
+                    ..Default::default()
+                });
+                sign_tx_with_key_pair(key_pair, tx)
+            })
+            .collect::<Vec<_>>();
+        let raw = txs.iter().map(|tx| tx.envelope_encoded()).collect();
+
+        let orderings =
+            eth_api.simulate_ordering(raw, BlockNumberOrTag::Latest.into()).await.unwrap();
+        assert_eq!(orderings.len(), 2);
+        for outcomes in &orderings {
+            assert_eq!(outcomes.len(), 2);
+            assert!(outcomes.iter().all(|outcome| outcome.error.is_none()));
+        }
+        assert_eq!(orderings[0][0].hash, txs[1].hash);
+        assert_eq!(orderings[1][0].hash, txs[0].hash);
+    }
+
+    #[tokio::test]
+    async fn test_simulate_blocks() {
+        // increments slot 0, emits an empty log and returns the new value:
+        // SSTORE(0, SLOAD(0) + 1) MSTORE(0, ..) LOG0(0, 0) RETURN(0, 32)
+        let contract = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Default::default());
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from_static(&hex!(
+                "6000546001018060005560005260006000a060206000f3"
+            ))),
+        );
+        let eth_api = build_test_eth_api(provider);
+
+        let call = CallRequest { to: Some(contract), ..Default::default() };
+        let overrides = BlockOverrides {
+            time: Some(U64::from(100)),
+            base_fee: Some(U256::from(7)),
+            ..Default::default()
+        };
+        let blocks = eth_api
+            .simulate_blocks(
+                BlockNumberOrTag::Latest.into(),
+                vec![
+                    (BlockOverrides::default(), vec![call.clone()]),
+                    (overrides, vec![call.clone(), call.clone()]),
+                ],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].number, U256::from(1));
+        assert_eq!(blocks[0].timestamp, U256::from(SIMULATED_BLOCK_TIME));
+        assert_eq!(blocks[1].number, U256::from(2));
+        assert_eq!(blocks[1].timestamp, U256::from(100));
+        assert_eq!(blocks[1].base_fee_per_gas, U256::from(7));
+
+        // the state changes are committed across calls and blocks
+        let outputs = blocks
+            .iter()
+            .flat_map(|block| &block.calls)
+            .map(|call| call.output.clone().unwrap())
+            .collect::<Vec<_>>();
+        let expected = (1..=3u64)
+            .map(|value| Bytes::from(U256::from(value).to_be_bytes::<32>().to_vec()))
+            .collect::<Vec<_>>();
+        assert_eq!(outputs, expected);
+
+        let logs = &blocks[1].calls[1].logs;
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].address, contract);
+        assert_eq!(logs[0].block_number, Some(U256::from(2)));
+        assert_eq!(logs[0].transaction_index, Some(U256::from(1)));
+        assert_eq!(logs[0].log_index, Some(U256::from(1)));
+        assert_eq!(blocks[1].gas_used, blocks[1].calls.iter().map(|call| call.gas_used).sum());
+
+        // block numbers can't go backwards
+        let overrides = BlockOverrides { number: Some(U256::ZERO), ..Default::default() };
+        let err = eth_api
+            .simulate_blocks(BlockNumberOrTag::Latest.into(), vec![(overrides, vec![call])])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EthApiError::InvalidParams(_)));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_gas_top_of_block() {
+        // PUSH1 42 PUSH1 1 SSTORE STOP
+        let contract = Address::random();
+        let code = Bytes::from_static(&hex!("602a60015500"));
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Default::default());
+        provider
+            .add_account(contract, ExtendedAccount::new(0, U256::ZERO).with_bytecode(code.clone()));
+        // a pending transaction already wrote the slot, which makes a second write cheaper
+        provider.add_pending_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(code)
+                .extend_storage([(B256::with_last_byte(1), U256::from(42))]),
+        );
+        let eth_api = build_test_eth_api(provider);
+
+        let request = CallRequest { to: Some(contract), ..Default::default() };
+        let latest = eth_api
+            .estimate_gas_at(request.clone(), BlockNumberOrTag::Latest.into(), None)
+            .await
+            .unwrap();
+        let pending = eth_api
+            .estimate_gas_at(request.clone(), BlockNumberOrTag::Pending.into(), None)
+            .await
+            .unwrap();
+        assert!(pending < latest);
+
+        // the top of the next block doesn't see the pending write
+        let estimate = eth_api.estimate_gas_top_of_block(request).await.unwrap();
+        assert_eq!(estimate, latest);
+        // the slot is still unset, so the write pays the full cost of a new slot
+        assert!(estimate > U256::from(MIN_TRANSACTION_GAS + 20_000));
+    }
+
+    #[tokio::test]
+    async fn test_create_access_list() {
+        // PUSH1 0 SLOAD STOP
+        let other = Address::random();
+        // CALL(gas, other, 0, 0, 0, 0, 0)
+        let call_other =
+            [&hex!("6000600060006000600073")[..], &other[..], &hex!("5af1")[..]].concat();
+        // reads its own slot 1 after calling `other`: PUSH1 1 SLOAD STOP
+        let contract = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Default::default());
+        provider.add_account(
+            other,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("60005400"))),
+        );
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode([&call_other[..], &hex!("60015400")[..]].concat().into()),
+        );
+        let eth_api = build_test_eth_api(provider);
+
+        let other_item = AccessListItem { address: other, storage_keys: vec![B256::ZERO] };
+
+        // the called contract's slots are included, sorted by address
+        let mut expected = vec![
+            other_item.clone(),
+            AccessListItem { address: contract, storage_keys: vec![B256::with_last_byte(1)] },
+        ];
+        expected.sort_unstable_by_key(|item| item.address);
+        let request = CallRequest { to: Some(contract), ..Default::default() };
+        let list = eth_api.create_access_list_at(request, None).await.unwrap();
+        assert_eq!(list.access_list, AccessList(expected));
+        assert!(list.gas_used > U256::from(MIN_TRANSACTION_GAS));
+
+        // the created contract doesn't read any storage
+        let expected = AccessList(vec![other_item]);
+        let request = CallRequest {
+            input: CallInput::new([&call_other[..], &hex!("00")[..]].concat().into()),
+            ..Default::default()
+        };
+        let list = eth_api.create_access_list_at(request, None).await.unwrap();
+        assert_eq!(list.access_list, expected);
+        assert!(list.excluded_precompiles.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_access_list_excluded_precompiles() {
+        // STATICCALL(gas, 0x1, 0, 0, 0, 0) to ecrecover
+        let contract = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Default::default());
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("600060006000600060015afa00"))),
+        );
+        let eth_api = build_test_eth_api(provider);
+
+        let request = CallRequest { to: Some(contract), ..Default::default() };
+        let list = eth_api.create_access_list_at(request, None).await.unwrap();
+
+        let ecrecover = Address::with_last_byte(1);
+        assert_eq!(list.excluded_precompiles, vec![ecrecover]);
+        assert!(list.access_list.0.iter().all(|item| item.address != ecrecover));
+    }
+
+    #[tokio::test]
+    async fn test_create_access_list_panic() {
+        // MSTORE(0, Panic selector) MSTORE(4, 0x11) REVERT(0, 36)
+        let contract = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Default::default());
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from_static(&hex!(
+                "7f4e487b7100000000000000000000000000000000000000000000000000000000600052601160045260246000fd"
+            ))),
+        );
+        let eth_api = build_test_eth_api(provider);
+
+        let request = CallRequest { to: Some(contract), ..Default::default() };
+        let err = eth_api.create_access_list_at(request, None).await.unwrap_err();
+        match err {
+            EthApiError::ExecutionReverted { reason, data } => {
+                assert_eq!(reason.as_deref(), Some("panic code 0x11"));
+                assert_eq!(data.map(|data| data.len()), Some(36));
+            }
+            err => panic!("unexpected error: {err:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_estimate_gas_with_access_list() {
+        // PUSH1 1 SLOAD STOP
+        let contract = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Default::default());
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("60015400"))),
+        );
+        let eth_api = build_test_eth_api(provider);
+
+        let request = CallRequest { to: Some(contract), ..Default::default() };
+        let res = eth_api
+            .estimate_gas_with_access_list_at(
+                request.clone(),
+                BlockNumberOrTag::Latest.into(),
+                None,
+            )
+            .await
+            .unwrap();
+        let expected = AccessList(vec![AccessListItem {
+            address: contract,
+            storage_keys: vec![B256::with_last_byte(1)],
+        }]);
+        assert_eq!(res.access_list, expected);
+
+        // the estimate includes the cost of the created access list
+        let with_list = CallRequest { access_list: Some(expected), ..request.clone() };
+        let estimate = eth_api
+            .estimate_gas_at(with_list, BlockNumberOrTag::Latest.into(), None)
+            .await
+            .unwrap();
+        assert_eq!(res.gas_used, estimate);
+
+        // PUSH1 2 SLOAD STOP
+        //
+        // the access list is created on top of the overridden state
+        let state_override = HashMap::from([(
+            contract,
+            AccountOverride {
+                code: Some(Bytes::from_static(&hex!("60025400"))),
+                ..Default::default()
+            },
+        )]);
+        let res = eth_api
+            .estimate_gas_with_access_list_at(
+                request,
+                BlockNumberOrTag::Latest.into(),
+                Some(state_override),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.access_list.0[0].storage_keys, vec![B256::with_last_byte(2)]);
+    }
+}
@@ -46,6 +46,62 @@ where
         self.gas_oracle().suggest_tip_cap().await
     }
 
+    /// Returns a fee suggestion for EIP-1559 transactions based on the fee history of the last
+    /// `blocks` blocks.
+    ///
+    /// The suggested priority fee is the median of the blocks' priority fees at
+    /// `reward_percentile`. Blocks without transactions have no priority fees, so they are left
+    /// out. If none of the blocks has transactions, the suggestion of the gas price oracle is
+    /// used instead. The suggested max fee leaves room for the base fee to double.
+    pub async fn suggested_fees(
+        &self,
+        blocks: u64,
+        reward_percentile: f64,
+    ) -> EthResult<FeeSuggestion> {
+        if blocks == 0 {
+            return Err(EthApiError::InvalidParams("block count must not be zero".to_string()))
+        }
+
+        let history = self
+            .fee_history(blocks, BlockNumberOrTag::Latest, Some(vec![reward_percentile]))
+            .await?;
+
+        let mut rewards = history
+            .gas_used_ratio
+            .iter()
+            .zip(history.reward.iter().flatten())
+            .filter(|(gas_used_ratio, _)| **gas_used_ratio > 0.0)
+            .filter_map(|(_, reward)| reward.first().copied())
+            .collect::<Vec<_>>();
+        rewards.sort_unstable();
+        let max_priority_fee_per_gas = if rewards.is_empty() {
+            self.suggested_priority_fee().await?
+        } else {
+            let mid = rewards.len() / 2;
+            if rewards.len() % 2 == 0 {
+                (rewards[mid - 1] + rewards[mid]) / U256::from(2)
+            } else {
+                rewards[mid]
+            }
+        };
+
+        // the last base fee is the one of the next block
+        let next_base_fee = history.base_fee_per_gas.last().copied().unwrap_or_default();
+        let oldest_base_fee = history.base_fee_per_gas.first().copied().unwrap_or_default();
+        let base_fee_trend = if oldest_base_fee.is_zero() {
+            0.0
+        } else {
+            next_base_fee.to::<u128>() as f64 / oldest_base_fee.to::<u128>() as f64 - 1.0
+        };
+
+        Ok(FeeSuggestion {
+            max_fee_per_gas: next_base_fee * U256::from(2) + max_priority_fee_per_gas,
+            max_priority_fee_per_gas,
+            next_base_fee_per_gas: next_base_fee,
+            base_fee_trend,
+        })
+    }
+
     /// Reports the fee history, for the given amount of blocks, up until the given newest block.
     ///
     /// If `reward_percentiles` are provided the [FeeHistory] will include the _approximated_
@@ -216,3 +272,17 @@ where
         entry.rewards.get(index).cloned().unwrap_or(U256::ZERO)
     }
 }
+
+/// An EIP-1559 fee suggestion, see [EthApi::suggested_fees].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeeSuggestion {
+    /// Suggested max fee per gas, twice the next block's base fee plus the priority fee.
+    pub max_fee_per_gas: U256,
+    /// Suggested max priority fee per gas.
+    pub max_priority_fee_per_gas: U256,
+    /// Base fee per gas of the next block.
+    pub next_base_fee_per_gas: U256,
+    /// Relative change of the base fee from the oldest block of the history to the next block,
+    /// for example `0.25` if the base fee rose by 25%.
+    pub base_fee_trend: f64,
+}
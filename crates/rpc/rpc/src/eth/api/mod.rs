@@ -17,15 +17,19 @@ use reth_interfaces::RethResult;
 use reth_network_api::NetworkInfo;
 use reth_primitives::{
     revm_primitives::{BlockEnv, CfgEnv},
-    Address, BlockId, BlockNumberOrTag, ChainInfo, SealedBlockWithSenders, B256, U256, U64,
+    Address, BlockId, BlockNumberOrTag, ChainInfo, SealedBlockWithSenders,
+    TransactionSignedEcRecovered, B256, U256, U64,
 };
 
 use reth_provider::{
     BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderBox, StateProviderFactory,
 };
+use reth_revm::tracing::TracingInspectorConfig;
 use reth_rpc_types::{SyncInfo, SyncStatus};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::TransactionPool;
+use schnellru::{ByLength, LruMap};
+use serde::{Deserialize, Serialize};
 use std::{
     fmt::Debug,
     future::Future,
@@ -48,7 +52,16 @@ mod state;
 mod transactions;
 
 use crate::BlockingTaskPool;
-pub use transactions::{EthTransactions, TransactionSource};
+pub use block::{FeeBreakdown, GasStats};
+pub use call::{
+    CallOutcome, SimulatedBlock, SimulatedCall, MAX_ORDERING_TRANSACTIONS, SIMULATED_BLOCK_TIME,
+};
+pub use fees::FeeSuggestion;
+pub use transactions::{
+    AccessSet, AdjacentReceipts, CallFees, CoverageReport, EligibilityReport, EthTransactions,
+    JumpPoint, TransactionSource, TransactionStatus, TxFee, TxOutcome, ACCOUNT_STATE_BYTES,
+    STORAGE_SLOT_STATE_BYTES,
+};
 
 /// `Eth` API trait.
 ///
@@ -110,6 +123,9 @@ where
             eth_cache,
             gas_oracle,
             gas_cap.into().into(),
+            TracingLimits::default(),
+            DEFAULT_MAX_RECOVERED_BLOCKS,
+            SequencerRetryConfig::default(),
             Box::<TokioTaskExecutor>::default(),
             blocking_task_pool,
             fee_history_cache,
@@ -125,6 +141,9 @@ where
         eth_cache: EthStateCache,
         gas_oracle: GasPriceOracle<Provider>,
         gas_cap: u64,
+        tracing_limits: TracingLimits,
+        max_recovered_blocks: u32,
+        sequencer_retry_config: SequencerRetryConfig,
         task_spawner: Box<dyn TaskSpawner>,
         blocking_task_pool: BlockingTaskPool,
         fee_history_cache: FeeHistoryCache,
@@ -145,9 +164,15 @@ where
             eth_cache,
             gas_oracle,
             gas_cap,
+            tracing_limits,
+            sequencer_retry_config,
             starting_block: U256::from(latest_block),
             task_spawner,
             pending_block: Default::default(),
+            transaction_arrivals: Mutex::new(LruMap::new(ByLength::new(
+                MAX_TRACKED_TRANSACTION_ARRIVALS,
+            ))),
+            recovered_transactions: Mutex::new(LruMap::new(ByLength::new(max_recovered_blocks))),
             blocking_task_pool,
             fee_history_cache,
             #[cfg(feature = "optimism")]
@@ -192,6 +217,16 @@ where
         self.inner.gas_cap
     }
 
+    /// Returns the configured limits for tracing requests
+    pub fn tracing_limits(&self) -> TracingLimits {
+        self.inner.tracing_limits
+    }
+
+    /// Returns the configured retry behaviour for forwarding transactions to the sequencer
+    pub fn sequencer_retry_config(&self) -> SequencerRetryConfig {
+        self.inner.sequencer_retry_config
+    }
+
     /// Returns the inner `Provider`
     pub fn provider(&self) -> &Provider {
         &self.inner.provider
@@ -211,6 +246,35 @@ where
     pub fn fee_history_cache(&self) -> &FeeHistoryCache {
         &self.inner.fee_history_cache
     }
+
+    /// Records the unix timestamp at which the transaction was submitted to the pool through this
+    /// API.
+    pub(crate) async fn record_transaction_arrival(&self, hash: B256, timestamp: u64) {
+        self.inner.transaction_arrivals.lock().await.insert(hash, timestamp);
+    }
+
+    /// Returns the unix timestamp at which the transaction was submitted to the pool through this
+    /// API, if it is still tracked.
+    pub(crate) async fn transaction_arrival(&self, hash: B256) -> Option<u64> {
+        self.inner.transaction_arrivals.lock().await.get(&hash).copied()
+    }
+
+    /// Caches the ecrecovered transactions of the block with the given hash.
+    pub(crate) async fn cache_recovered_transactions(
+        &self,
+        block_hash: B256,
+        transactions: Arc<Vec<TransactionSignedEcRecovered>>,
+    ) {
+        self.inner.recovered_transactions.lock().await.insert(block_hash, transactions);
+    }
+
+    /// Returns the cached ecrecovered transactions of the block with the given hash, if any.
+    pub(crate) async fn cached_recovered_transactions(
+        &self,
+        block_hash: B256,
+    ) -> Option<Arc<Vec<TransactionSignedEcRecovered>>> {
+        self.inner.recovered_transactions.lock().await.get(&block_hash).cloned()
+    }
 }
 
 // === State access helpers ===
@@ -298,6 +362,53 @@ where
         Ok(PendingBlockEnv { cfg, block_env, origin })
     }
 
+    /// Configures the [CfgEnv] and [BlockEnv] for a block built on top of the `latest` block at
+    /// the given timestamp.
+    ///
+    /// Unlike [Self::pending_block_env_and_cfg], which assumes the child block is in the next slot,
+    /// this allows simulating a block further in the future. The base fee is derived from the
+    /// `latest` block with the EIP-1559 parameters active at the timestamp.
+    ///
+    /// Returns the env and the [BlockId] of the `latest` block, which holds the state the block is
+    /// built on.
+    pub async fn evm_env_for_pending_at(
+        &self,
+        timestamp: u64,
+    ) -> EthResult<(CfgEnv, BlockEnv, BlockId)> {
+        let latest =
+            self.provider().latest_header()?.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        if timestamp <= latest.timestamp {
+            return Err(EthApiError::InvalidParams(format!(
+                "timestamp {timestamp} is not after the latest block timestamp {}",
+                latest.timestamp
+            )))
+        }
+
+        let chain_spec = self.provider().chain_spec();
+        let base_fee = latest.next_block_base_fee(chain_spec.base_fee_params(timestamp));
+
+        let mut header = latest.clone().unseal();
+        header.parent_hash = latest.hash;
+        header.number += 1;
+        header.timestamp = timestamp;
+        header.base_fee_per_gas = base_fee;
+
+        let mut cfg = CfgEnv::default();
+
+        #[cfg(feature = "optimism")]
+        {
+            cfg.optimism = chain_spec.is_optimism();
+        }
+
+        let mut block_env = BlockEnv::default();
+        self.provider().fill_env_with_header(&mut cfg, &mut block_env, &header)?;
+        block_env.number = U256::from(header.number);
+        block_env.timestamp = U256::from(timestamp);
+        block_env.basefee = U256::from(base_fee.unwrap_or_default());
+
+        Ok((cfg, block_env, latest.hash.into()))
+    }
+
     /// Returns the locally built pending block
     pub(crate) async fn local_pending_block(&self) -> EthResult<Option<SealedBlockWithSenders>> {
         let pending = self.pending_block_env_and_cfg()?;
@@ -420,6 +531,12 @@ where
 /// more complex calls.
 pub const RPC_DEFAULT_GAS_CAP: GasCap = GasCap(50_000_000);
 
+/// The maximum number of submitted transactions for which the arrival time is tracked.
+const MAX_TRACKED_TRANSACTION_ARRIVALS: u32 = 10_000;
+
+/// The default number of blocks for which the ecrecovered transactions are cached for tracing.
+pub const DEFAULT_MAX_RECOVERED_BLOCKS: u32 = 500;
+
 /// The wrapper type for gas limit
 #[derive(Debug, Clone, Copy)]
 pub struct GasCap(u64);
@@ -442,6 +559,64 @@ impl From<GasCap> for u64 {
     }
 }
 
+/// The default wall-clock time after which a blocking tracing task is abandoned.
+pub const DEFAULT_TRACING_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// Limits for tracing requests, so that a single request can't exhaust the node's memory or
+/// occupy a blocking thread indefinitely.
+///
+/// Traces exceeding the step or memory limit fail with [EthApiError::TracingTimeoutOrLimit],
+/// tasks exceeding the timeout fail with [EthApiError::TracingTimeout]. Steps and memory are
+/// unlimited by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TracingLimits {
+    /// Maximum number of steps recorded by a single trace, unlimited if `None`.
+    pub max_steps: Option<usize>,
+    /// Maximum number of memory bytes recorded by a single trace, unlimited if `None`.
+    pub max_memory_bytes: Option<usize>,
+    /// Maximum wall-clock time a blocking tracing task may take, unlimited if `None`.
+    pub timeout: Option<Duration>,
+}
+
+impl TracingLimits {
+    /// Applies the limits to the given [TracingInspectorConfig].
+    pub fn apply(&self, config: TracingInspectorConfig) -> TracingInspectorConfig {
+        config.set_max_steps(self.max_steps).set_max_memory_bytes(self.max_memory_bytes)
+    }
+}
+
+impl Default for TracingLimits {
+    fn default() -> Self {
+        Self { max_steps: None, max_memory_bytes: None, timeout: Some(DEFAULT_TRACING_TIMEOUT) }
+    }
+}
+
+/// Retry behaviour for forwarding transactions to the sequencer.
+///
+/// Connection errors and `5xx` responses are retried with exponential backoff and jitter, while
+/// `4xx` responses and JSON-RPC errors returned by the sequencer are surfaced immediately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SequencerRetryConfig {
+    /// Maximum number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Backoff before the first retry, doubled on every subsequent retry.
+    pub initial_backoff: Duration,
+    /// Upper bound for the backoff between two attempts.
+    pub max_backoff: Duration,
+}
+
+impl Default for SequencerRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
 /// Container type `EthApi`
 struct EthApiInner<Provider, Pool, Network> {
     /// The transaction pool.
@@ -458,12 +633,20 @@ struct EthApiInner<Provider, Pool, Network> {
     gas_oracle: GasPriceOracle<Provider>,
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     gas_cap: u64,
+    /// Limits for tracing RPC methods.
+    tracing_limits: TracingLimits,
+    /// Retry behaviour for forwarding transactions to the sequencer.
+    sequencer_retry_config: SequencerRetryConfig,
     /// The block number at which the node started
     starting_block: U256,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
     /// Cached pending block if any
     pending_block: Mutex<Option<PendingBlock>>,
+    /// Arrival times of transactions submitted through this API
+    transaction_arrivals: Mutex<LruMap<B256, u64, ByLength>>,
+    /// Ecrecovered transactions of recently traced blocks, keyed by block hash
+    recovered_transactions: Mutex<LruMap<B256, Arc<Vec<TransactionSignedEcRecovered>>, ByLength>>,
     /// A pool dedicated to blocking tasks.
     blocking_task_pool: BlockingTaskPool,
     /// Cache for block fees history
@@ -472,3 +655,62 @@ struct EthApiInner<Provider, Pool, Network> {
     #[cfg(feature = "optimism")]
     http_client: reqwest::Client,
 }
+
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use super::*;
+    use crate::eth::FeeHistoryCacheConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::constants::ETHEREUM_BLOCK_GAS_LIMIT;
+    use reth_provider::BlockReader;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    /// Returns an [EthApi] over the given provider with a testing pool and default settings.
+    pub(crate) fn build_test_eth_api<
+        P: BlockReaderIdExt
+            + BlockReader
+            + ChainSpecProvider
+            + EvmEnvProvider
+            + StateProviderFactory
+            + Unpin
+            + Clone
+            + 'static,
+    >(
+        provider: P,
+    ) -> EthApi<P, TestPool, NoopNetwork> {
+        build_test_eth_api_with_limits(provider, TracingLimits::default())
+    }
+
+    /// Same as [build_test_eth_api] but with the given [TracingLimits].
+    pub(crate) fn build_test_eth_api_with_limits<
+        P: BlockReaderIdExt
+            + BlockReader
+            + ChainSpecProvider
+            + EvmEnvProvider
+            + StateProviderFactory
+            + Unpin
+            + Clone
+            + 'static,
+    >(
+        provider: P,
+        tracing_limits: TracingLimits,
+    ) -> EthApi<P, TestPool, NoopNetwork> {
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let fee_history_cache =
+            FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
+        EthApi::with_spawner(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            tracing_limits,
+            DEFAULT_MAX_RECOVERED_BLOCKS,
+            Default::default(),
+            Box::<TokioTaskExecutor>::default(),
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            fee_history_cache,
+        )
+    }
+}
@@ -1,5 +1,8 @@
+use crate::eth::{api::SequencerRetryConfig, error::OptimismEthApiError};
+use rand::Rng;
 use reth_primitives::U256;
 use revm::L1BlockInfo;
+use std::time::Duration;
 
 /// Optimism Transaction Metadata
 ///
@@ -28,3 +31,245 @@ impl OptimismTxMeta {
         Self { l1_block_info, l1_fee, l1_data_gas }
     }
 }
+
+impl SequencerRetryConfig {
+    /// Returns the backoff to wait before the given retry, starting at `0` for the first retry.
+    ///
+    /// A random jitter of up to half the backoff is added so that concurrent requests don't retry
+    /// in lockstep.
+    fn backoff(&self, retry: u32) -> Duration {
+        let backoff = self.initial_backoff.saturating_mul(1 << retry.min(16)).min(self.max_backoff);
+        let jitter = rand::thread_rng().gen_range(0..=backoff.as_millis() as u64 / 2);
+        backoff + Duration::from_millis(jitter)
+    }
+}
+
+/// A JSON-RPC response returned by the sequencer, only the error is of interest.
+#[derive(Debug, serde::Deserialize)]
+struct SequencerResponse {
+    #[serde(default)]
+    error: Option<SequencerRpcError>,
+}
+
+/// A JSON-RPC error object returned by the sequencer.
+#[derive(Debug, serde::Deserialize)]
+struct SequencerRpcError {
+    code: i32,
+    message: String,
+    #[serde(default)]
+    data: Option<serde_json::Value>,
+}
+
+/// A JSON-RPC response to a single request of a batch sent to the sequencer.
+#[derive(Debug, serde::Deserialize)]
+struct SequencerBatchResponse {
+    id: usize,
+    #[serde(default)]
+    error: Option<SequencerRpcError>,
+}
+
+impl From<SequencerRpcError> for OptimismEthApiError {
+    fn from(err: SequencerRpcError) -> Self {
+        OptimismEthApiError::SequencerError { code: err.code, message: err.message, data: err.data }
+    }
+}
+
+/// Posts the JSON-RPC `body` to the sequencer `endpoint`, retrying according to `retry`.
+///
+/// If the sequencer responds with a JSON-RPC error object, it is returned as
+/// [OptimismEthApiError::SequencerError] so that it can be passed through to the caller.
+pub(crate) async fn send_to_sequencer(
+    client: &reqwest::Client,
+    endpoint: &str,
+    body: String,
+    retry: &SequencerRetryConfig,
+) -> Result<(), OptimismEthApiError> {
+    let (status, response_body) = post_to_sequencer(client, endpoint, body, retry).await?;
+    if let Ok(SequencerResponse { error: Some(err) }) = serde_json::from_slice(&response_body) {
+        return Err(err.into())
+    }
+    if status.is_client_error() {
+        return Err(OptimismEthApiError::SequencerStatus(status.as_u16()))
+    }
+    Ok(())
+}
+
+/// Posts the JSON-RPC batch `body` to the sequencer `endpoint`, retrying according to `retry`.
+///
+/// The requests of the batch must use their index in the batch as `id`. Returns the result of
+/// every request of the batch in order, so that a request rejected by the sequencer doesn't fail
+/// the other requests. If the sequencer rejects the batch as a whole, the error is returned for
+/// the entire batch.
+pub(crate) async fn send_batch_to_sequencer(
+    client: &reqwest::Client,
+    endpoint: &str,
+    body: String,
+    len: usize,
+    retry: &SequencerRetryConfig,
+) -> Result<Vec<Result<(), OptimismEthApiError>>, OptimismEthApiError> {
+    let (status, response_body) = post_to_sequencer(client, endpoint, body, retry).await?;
+    let mut results = (0..len).map(|_| Ok(())).collect::<Vec<_>>();
+    if let Ok(responses) = serde_json::from_slice::<Vec<SequencerBatchResponse>>(&response_body) {
+        for response in responses {
+            if let (Some(result), Some(err)) = (results.get_mut(response.id), response.error) {
+                *result = Err(err.into());
+            }
+        }
+        return Ok(results)
+    }
+    if let Ok(SequencerResponse { error: Some(err) }) = serde_json::from_slice(&response_body) {
+        return Err(err.into())
+    }
+    if status.is_client_error() {
+        return Err(OptimismEthApiError::SequencerStatus(status.as_u16()))
+    }
+    Ok(results)
+}
+
+/// Posts the `body` to the sequencer `endpoint` and returns the status and body of the response.
+///
+/// Connection errors and `5xx` responses are retried according to `retry`.
+async fn post_to_sequencer(
+    client: &reqwest::Client,
+    endpoint: &str,
+    body: String,
+    retry: &SequencerRetryConfig,
+) -> Result<(http::StatusCode, Vec<u8>), OptimismEthApiError> {
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let err = match client
+            .post(endpoint)
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(body.clone())
+            .send()
+            .await
+        {
+            Ok(response) => {
+                let status = response.status();
+                if status.is_server_error() {
+                    OptimismEthApiError::SequencerStatus(status.as_u16())
+                } else {
+                    return Ok((status, response.bytes().await?.to_vec()))
+                }
+            }
+            Err(err) => err.into(),
+        };
+
+        if attempt >= retry.max_attempts {
+            return Err(err)
+        }
+        let backoff = retry.backoff(attempt - 1);
+        tracing::debug!(
+            target: "rpc::eth",
+            %err,
+            attempt,
+            ?backoff,
+            "Failed to forward transaction to sequencer, retrying"
+        );
+        tokio::time::sleep(backoff).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// Spawns an HTTP server that answers consecutive requests with the given responses and
+    /// returns its endpoint.
+    async fn spawn_server(responses: Vec<(u16, &'static str)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf).await.unwrap();
+                let response = format!(
+                    "HTTP/1.1 {status} X\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+                stream.shutdown().await.unwrap();
+            }
+        });
+        endpoint
+    }
+
+    fn fast_retry() -> SequencerRetryConfig {
+        SequencerRetryConfig {
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_on_server_error() {
+        let endpoint =
+            spawn_server(vec![(503, ""), (200, r#"{"jsonrpc":"2.0","id":1,"result":"0x01"}"#)])
+                .await;
+
+        send_to_sequencer(&reqwest::Client::new(), &endpoint, "{}".to_string(), &fast_retry())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let endpoint = spawn_server(vec![(503, ""), (502, ""), (503, "")]).await;
+
+        let err =
+            send_to_sequencer(&reqwest::Client::new(), &endpoint, "{}".to_string(), &fast_retry())
+                .await
+                .unwrap_err();
+        assert!(matches!(err, OptimismEthApiError::SequencerStatus(503)));
+    }
+
+    #[tokio::test]
+    async fn surfaces_sequencer_error_without_retry() {
+        let endpoint = spawn_server(vec![(
+            400,
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"nonce too low"}}"#,
+        )])
+        .await;
+
+        let err =
+            send_to_sequencer(&reqwest::Client::new(), &endpoint, "{}".to_string(), &fast_retry())
+                .await
+                .unwrap_err();
+        assert!(matches!(
+            err,
+            OptimismEthApiError::SequencerError { code: -32000, ref message, data: None }
+                if message == "nonce too low"
+        ));
+    }
+
+    #[tokio::test]
+    async fn batch_reports_errors_per_request() {
+        let endpoint = spawn_server(vec![(
+            200,
+            r#"[{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"nonce too low"}},{"jsonrpc":"2.0","id":0,"result":"0x01"}]"#,
+        )])
+        .await;
+
+        let results = send_batch_to_sequencer(
+            &reqwest::Client::new(),
+            &endpoint,
+            "[]".to_string(),
+            2,
+            &fast_retry(),
+        )
+        .await
+        .unwrap();
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(OptimismEthApiError::SequencerError { code: -32000, .. })
+        ));
+    }
+}
@@ -328,7 +328,7 @@ impl PendingBlockEnvOrigin {
     }
 
     /// Returns the hash of the block the pending block should be built on.
-    fn build_target_hash(&self) -> B256 {
+    pub(crate) fn build_target_hash(&self) -> B256 {
         match self {
             PendingBlockEnvOrigin::ActualPending(block) => block.parent_hash,
             PendingBlockEnvOrigin::DerivedFromLatest(header) => header.hash,
@@ -157,6 +157,12 @@ where
         Ok(EthTransactions::transaction_by_hash(self, hash).await?.map(Into::into))
     }
 
+    /// Handler for: `eth_getRawTransactionByHash`
+    async fn raw_transaction_by_hash(&self, hash: B256) -> Result<Option<Bytes>> {
+        trace!(target: "rpc::eth", ?hash, "Serving eth_getRawTransactionByHash");
+        Ok(EthTransactions::raw_transaction_by_hash(self, hash).await?)
+    }
+
     /// Handler for: `eth_getTransactionByBlockHashAndIndex`
     async fn transaction_by_block_hash_and_index(
         &self,
@@ -392,56 +398,18 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::{
-        eth::{
-            cache::EthStateCache, gas_oracle::GasPriceOracle, FeeHistoryCache,
-            FeeHistoryCacheConfig,
-        },
-        BlockingTaskPool, EthApi,
-    };
+    use crate::{eth::api::test_utils::build_test_eth_api, EthApi};
     use jsonrpsee::types::error::INVALID_PARAMS_CODE;
     use reth_interfaces::test_utils::{generators, generators::Rng};
     use reth_network_api::noop::NoopNetwork;
     use reth_primitives::{
-        basefee::calculate_next_block_base_fee, constants::ETHEREUM_BLOCK_GAS_LIMIT, BaseFeeParams,
-        Block, BlockNumberOrTag, Header, TransactionSigned, B256, U256,
-    };
-    use reth_provider::{
-        test_utils::{MockEthProvider, NoopProvider},
-        BlockReader, BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory,
+        basefee::calculate_next_block_base_fee, BaseFeeParams, Block, BlockNumberOrTag, Header,
+        Receipt, TransactionSigned, B256, U256,
     };
+    use reth_provider::test_utils::{MockEthProvider, NoopProvider};
     use reth_rpc_api::EthApiServer;
     use reth_rpc_types::FeeHistory;
-    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
-
-    fn build_test_eth_api<
-        P: BlockReaderIdExt
-            + BlockReader
-            + ChainSpecProvider
-            + EvmEnvProvider
-            + StateProviderFactory
-            + Unpin
-            + Clone
-            + 'static,
-    >(
-        provider: P,
-    ) -> EthApi<P, TestPool, NoopNetwork> {
-        let cache = EthStateCache::spawn(provider.clone(), Default::default());
-
-        let fee_history_cache =
-            FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
-
-        EthApi::new(
-            provider.clone(),
-            testing_pool(),
-            NoopNetwork::default(),
-            cache.clone(),
-            GasPriceOracle::new(provider.clone(), Default::default(), cache.clone()),
-            ETHEREUM_BLOCK_GAS_LIMIT,
-            BlockingTaskPool::build().expect("failed to build tracing pool"),
-            fee_history_cache,
-        )
-    }
+    use reth_transaction_pool::test_utils::TestPool;
 
     // Function to prepare the EthApi with mock data
     fn prepare_eth_api(
@@ -690,4 +658,65 @@ mod tests {
             "all: no percentiles were requested, so there should be no rewards result"
         );
     }
+
+    #[tokio::test]
+    async fn test_suggested_fees() {
+        let provider = MockEthProvider::default();
+        // the latest block is empty, so it has no priority fees to sample
+        for (number, tip) in [(0, Some(10)), (1, Some(30)), (2, None)] {
+            let (body, receipts) = tip
+                .map(|tip| {
+                    let tx = TransactionSigned {
+                        transaction: reth_primitives::Transaction::Eip1559(
+                            reth_primitives::TxEip1559 {
+                                max_priority_fee_per_gas: tip,
+                                max_fee_per_gas: 2_000,
+                                ..Default::default()
+                            },
+                        ),
+                        ..Default::default()
+                    };
+                    let receipt = Receipt {
+                        success: true,
+                        cumulative_gas_used: 21_000,
+                        ..Default::default()
+                    };
+                    (vec![tx], vec![receipt])
+                })
+                .unwrap_or_default();
+            let header = Header {
+                number,
+                gas_limit: 30_000_000,
+                gas_used: if body.is_empty() { 0 } else { 21_000 },
+                base_fee_per_gas: Some(1_000),
+                ..Default::default()
+            };
+            let hash = header.hash_slow();
+            provider.add_block(hash, Block { header, body, ..Default::default() });
+            provider.add_receipts(hash, receipts);
+        }
+
+        let eth_api = build_test_eth_api(provider);
+
+        let suggestion = eth_api.suggested_fees(3, 50.0).await.unwrap();
+        let next_base_fee = U256::from(calculate_next_block_base_fee(
+            0,
+            30_000_000,
+            1_000,
+            BaseFeeParams::ethereum(),
+        ));
+        // the median of the tips of the two non-empty blocks
+        assert_eq!(suggestion.max_priority_fee_per_gas, U256::from(20));
+        assert_eq!(suggestion.next_base_fee_per_gas, next_base_fee);
+        assert_eq!(suggestion.max_fee_per_gas, next_base_fee * U256::from(2) + U256::from(20));
+        // the base fee falls because the latest block is below its gas target
+        assert!(suggestion.base_fee_trend < 0.0);
+
+        // only the empty block is sampled, so the oracle's suggestion is used
+        let suggestion = eth_api.suggested_fees(1, 50.0).await.unwrap();
+        let oracle_tip = eth_api.suggested_priority_fee().await.unwrap();
+        assert_eq!(suggestion.max_priority_fee_per_gas, oracle_tip);
+
+        assert!(eth_api.suggested_fees(0, 50.0).await.is_err());
+    }
 }
@@ -2,10 +2,10 @@
 use crate::{
     eth::{
         api::pending_block::PendingBlockEnv,
-        error::{EthApiError, EthResult, SignError},
+        error::{decode_revert_output, EthApiError, EthResult, SignError},
         revm_utils::{
-            inspect, inspect_and_return_db, prepare_call_env, replay_transactions_until, transact,
-            EvmOverrides,
+            apply_evm_overrides, clone_into_empty_db, inspect, inspect_and_return_db,
+            prepare_call_env, replay_transactions_until, transact, with_cached_state, EvmOverrides,
         },
         utils::recover_raw_transaction,
     },
@@ -15,10 +15,13 @@ use async_trait::async_trait;
 use reth_network_api::NetworkInfo;
 use reth_primitives::{
     eip4844::calc_blob_gasprice,
-    revm::env::{fill_block_env_with_coinbase, tx_env_with_recovered},
+    revm::{
+        compat::{calculate_intrinsic_gas, calculate_intrinsic_gas_after_merge},
+        env::{fill_block_env_with_coinbase, tx_env_with_recovered},
+    },
     revm_primitives::{db::DatabaseCommit, Env, ExecutionResult, ResultAndState, SpecId, State},
-    Address, BlockId, BlockNumberOrTag, Bytes, FromRecoveredPooledTransaction, Header,
-    IntoRecoveredTransaction, Receipt, SealedBlock, SealedBlockWithSenders,
+    Address, BlockId, BlockNumberOrTag, Bytes, FromRecoveredPooledTransaction, Hardfork, Header,
+    IntoRecoveredTransaction, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader,
     TransactionKind::{Call, Create},
     TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, B256, U128, U256, U64,
 };
@@ -27,22 +30,35 @@ use reth_provider::{
 };
 use reth_revm::{
     database::StateProviderDatabase,
-    tracing::{TracingInspector, TracingInspectorConfig},
+    tracing::{StackSnapshotType, TracingInspector, TracingInspectorConfig},
 };
 use reth_rpc_types::{
+    trace::geth::{CallConfig, CallFrame, PreStateConfig, PreStateFrame},
     CallRequest, Index, Log, Transaction, TransactionInfo, TransactionReceipt, TransactionRequest,
     TypedTransactionRequest,
 };
-use reth_rpc_types_compat::transaction::from_recovered_with_block_context;
+use reth_rpc_types_compat::transaction::{from_recovered, from_recovered_with_block_context};
 use reth_transaction_pool::{TransactionOrigin, TransactionPool};
 use revm::{
-    db::CacheDB,
-    primitives::{BlockEnv, CfgEnv},
-    Inspector,
+    db::{CacheDB, DatabaseRef},
+    interpreter::{
+        gas::{ACCESS_LIST_ADDRESS, ACCESS_LIST_STORAGE_KEY},
+        opcode, CallInputs, CreateInputs, Gas, InstructionResult, Interpreter,
+    },
+    primitives::{BlockEnv, CfgEnv, TransactTo},
+    Database, EVMData, Inspector,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 #[cfg(feature = "optimism")]
-use crate::eth::api::optimism::OptimismTxMeta;
+use crate::eth::api::optimism::{send_batch_to_sequencer, send_to_sequencer, OptimismTxMeta};
+#[cfg(feature = "optimism")]
+use reth_primitives::ChainSpec;
 #[cfg(feature = "optimism")]
 use reth_revm::optimism::RethL1BlockInfo;
 #[cfg(feature = "optimism")]
@@ -123,6 +139,13 @@ pub trait EthTransactions: Send + Sync {
     /// Returns `Ok(None)` if no matching transaction was found.
     async fn transaction_by_hash(&self, hash: B256) -> EthResult<Option<TransactionSource>>;
 
+    /// Returns the EIP-2718 encoded transaction by hash.
+    ///
+    /// Checks the pool and state.
+    ///
+    /// Returns `Ok(None)` if no matching transaction was found.
+    async fn raw_transaction_by_hash(&self, hash: B256) -> EthResult<Option<Bytes>>;
+
     /// Returns the transaction by including its corresponding [BlockId]
     ///
     /// Note: this supports pending transactions
@@ -143,13 +166,37 @@ pub trait EthTransactions: Send + Sync {
     /// Note: The tx receipt is not available for pending transactions.
     async fn transaction_receipt(&self, hash: B256) -> EthResult<Option<TransactionReceipt>>;
 
-    /// Decodes and recovers the transaction and submits it to the pool.
+    /// Returns all transaction receipts in the block.
+    ///
+    /// The block and its receipts are fetched once and shared by all receipts of the block.
+    ///
+    /// Returns `None` if the block wasn't found.
+    async fn block_receipts(&self, block_id: BlockId)
+        -> EthResult<Option<Vec<TransactionReceipt>>>;
+
+    /// Decodes and recovers the transaction and submits it to the pool with a
+    /// [TransactionOrigin::Local] origin.
     ///
     /// Returns the hash of the transaction.
     async fn send_raw_transaction(&self, tx: Bytes) -> EthResult<B256>;
 
+    /// Decodes and recovers the transaction and submits it to the pool with the given origin.
+    ///
+    /// Transactions from untrusted clients should be submitted as [TransactionOrigin::External],
+    /// so they don't get the priority and exemptions from pool limits of local transactions.
+    ///
+    /// Returns the hash of the transaction.
+    async fn send_raw_transaction_with_origin(
+        &self,
+        tx: Bytes,
+        origin: TransactionOrigin,
+    ) -> EthResult<B256>;
+
     /// Signs transaction with a matching signer, if any and submits the transaction to the pool.
     /// Returns the hash of the signed transaction.
+    ///
+    /// Note: EIP-7702 set-code transactions are not supported, the request and transaction types
+    /// can't carry an authorization list yet.
     async fn send_transaction(&self, request: TransactionRequest) -> EthResult<B256>;
 
     /// Prepares the state and env for the given [CallRequest] at the given [BlockId] and executes
@@ -165,6 +212,26 @@ pub trait EthTransactions: Send + Sync {
         F: FnOnce(StateCacheDB, Env) -> EthResult<R> + Send + 'static,
         R: Send + 'static;
 
+    /// Prepares the state at the given [BlockId] once and executes the [CallRequest]s on top of
+    /// it in order, on a new task.
+    ///
+    /// The state changes of every call are committed before the next call is executed, so later
+    /// calls observe the effects of earlier ones. The state overrides are applied once before the
+    /// first call, the block overrides apply to every call.
+    ///
+    /// The batch stops after the first call that reverts or halts, the closure is then called
+    /// with the results of all calls executed so far, including the failed one.
+    async fn spawn_with_call_batch_at<F, R>(
+        &self,
+        requests: Vec<CallRequest>,
+        at: BlockId,
+        overrides: EvmOverrides,
+        f: F,
+    ) -> EthResult<R>
+    where
+        F: FnOnce(StateCacheDB, Vec<ResultAndState>) -> EthResult<R> + Send + 'static,
+        R: Send + 'static;
+
     /// Executes the call request at the given [BlockId].
     async fn transact_call_at(
         &self,
@@ -188,6 +255,10 @@ pub trait EthTransactions: Send + Sync {
     /// Executes the transaction on top of the given [BlockId] with a tracer configured by the
     /// config.
     ///
+    /// The [EvmOverrides] are applied to the state and the block env before the transaction is
+    /// executed. The block overrides can change fields like the number, timestamp, base fee and
+    /// coinbase of the block.
+    ///
     /// The callback is then called with the [TracingInspector] and the [ResultAndState] after the
     /// configured [Env] was inspected.
     ///
@@ -197,6 +268,7 @@ pub trait EthTransactions: Send + Sync {
         env: Env,
         config: TracingInspectorConfig,
         at: BlockId,
+        overrides: EvmOverrides,
         f: F,
     ) -> EthResult<R>
     where
@@ -205,7 +277,7 @@ pub trait EthTransactions: Send + Sync {
     /// Same as [Self::trace_at] but also provides the used database to the callback.
     ///
     /// Executes the transaction on top of the given [BlockId] with a tracer configured by the
-    /// config.
+    /// config, after applying the [EvmOverrides].
     ///
     /// The callback is then called with the [TracingInspector] and the [ResultAndState] after the
     /// configured [Env] was inspected.
@@ -214,6 +286,7 @@ pub trait EthTransactions: Send + Sync {
         env: Env,
         config: TracingInspectorConfig,
         at: BlockId,
+        overrides: EvmOverrides,
         f: F,
     ) -> EthResult<R>
     where
@@ -299,6 +372,39 @@ pub trait EthTransactions: Send + Sync {
             + Send
             + 'static,
         R: Send + 'static;
+
+    /// Executes all transactions of a block like [Self::trace_block_until], but traces the
+    /// transactions in parallel.
+    ///
+    /// This
+    /// 1. splits the transactions into at most one chunk per thread of the tracing pool
+    /// 2. executes the transactions sequentially without tracing to capture the state right
+    /// _before_ every chunk
+    /// 3. traces the transactions of every chunk sequentially on a separate blocking task on top
+    /// of the chunk's captured state
+    ///
+    /// The results are ordered by transaction index and the [TracingLimits::timeout] applies to
+    /// the entire call. Since all transactions are executed twice, this only pays off if the
+    /// tracer is more expensive than the plain execution.
+    async fn trace_block_until_par<F, R>(
+        &self,
+        block_id: BlockId,
+        highest_index: Option<u64>,
+        config: TracingInspectorConfig,
+        f: F,
+    ) -> EthResult<Option<Vec<R>>>
+    where
+        F: for<'a> Fn(
+                TransactionInfo,
+                TracingInspector,
+                ExecutionResult,
+                &'a State,
+                &'a CacheDB<StateProviderDatabase<StateProviderBox>>,
+            ) -> EthResult<R>
+            + Send
+            + Sync
+            + 'static,
+        R: Send + 'static;
 }
 
 #[async_trait]
@@ -426,6 +532,10 @@ where
         Ok(resp)
     }
 
+    async fn raw_transaction_by_hash(&self, hash: B256) -> EthResult<Option<Bytes>> {
+        Ok(self.transaction_by_hash(hash).await?.map(|tx| tx.into_recovered().envelope_encoded()))
+    }
+
     async fn transaction_by_hash_at(
         &self,
         transaction_hash: B256,
@@ -495,7 +605,78 @@ where
         self.build_transaction_receipt(tx, meta, receipt).await.map(Some)
     }
 
+    async fn block_receipts(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<Vec<TransactionReceipt>>> {
+        let mut block_and_receipts = None;
+
+        if block_id.is_pending() {
+            block_and_receipts = self.provider().pending_block_and_receipts()?;
+        } else if let Some(block_hash) = self.provider().block_hash_for_id(block_id)? {
+            block_and_receipts = self.cache().get_block_and_receipts(block_hash).await?;
+        }
+
+        let Some((block, receipts)) = block_and_receipts else { return Ok(None) };
+
+        let block_number = block.number;
+        let base_fee = block.base_fee_per_gas;
+        let block_hash = block.hash;
+        let excess_blob_gas = block.excess_blob_gas;
+
+        #[cfg(feature = "optimism")]
+        let (block_timestamp, l1_block_info) = {
+            let body = reth_revm::optimism::parse_l1_info_tx(
+                &block.body.first().ok_or(EthApiError::InternalEthError)?.input()[4..],
+            );
+            (block.timestamp, body.ok())
+        };
+
+        // the gas used and the index of the first log of a receipt depend on all previous
+        // receipts, so they're accumulated in a single pass over the block
+        let mut prev_cumulative_gas_used = 0;
+        let mut log_index = 0;
+        let mut block_receipts = Vec::with_capacity(block.body.len());
+        for (idx, (tx, receipt)) in block.body.into_iter().zip(receipts).enumerate() {
+            let meta = TransactionMeta {
+                tx_hash: tx.hash,
+                index: idx as u64,
+                block_hash,
+                block_number,
+                base_fee,
+                excess_blob_gas,
+            };
+            let gas_used = receipt.cumulative_gas_used - prev_cumulative_gas_used;
+            prev_cumulative_gas_used = receipt.cumulative_gas_used;
+            let first_log_index = log_index;
+            log_index += receipt.logs.len();
+
+            #[cfg(feature = "optimism")]
+            let op_tx_meta = self.build_op_tx_meta(&tx, l1_block_info.clone(), block_timestamp)?;
+
+            block_receipts.push(build_transaction_receipt_at(
+                tx,
+                meta,
+                receipt,
+                gas_used,
+                first_log_index,
+                #[cfg(feature = "optimism")]
+                op_tx_meta,
+            )?);
+        }
+
+        Ok(Some(block_receipts))
+    }
+
     async fn send_raw_transaction(&self, tx: Bytes) -> EthResult<B256> {
+        self.send_raw_transaction_with_origin(tx, TransactionOrigin::Local).await
+    }
+
+    async fn send_raw_transaction_with_origin(
+        &self,
+        tx: Bytes,
+        origin: TransactionOrigin,
+    ) -> EthResult<B256> {
         // On optimism, transactions are forwarded directly to the sequencer to be included in
         // blocks that it builds.
         #[cfg(feature = "optimism")]
@@ -504,8 +685,8 @@ where
         let recovered = recover_raw_transaction(tx)?;
         let pool_transaction = <Pool::Transaction>::from_recovered_pooled_transaction(recovered);
 
-        // submit the transaction to the pool with a `Local` origin
-        let hash = self.pool().add_transaction(TransactionOrigin::Local, pool_transaction).await?;
+        let hash = self.pool().add_transaction(origin, pool_transaction).await?;
+        self.record_transaction_arrival(hash, unix_timestamp()).await;
 
         Ok(hash)
     }
@@ -524,8 +705,9 @@ where
             request.nonce = Some(U64::from(nonce.to::<u64>()));
         }
 
+        self.fill_missing_fees(&mut request).await?;
+
         let chain_id = self.chain_id();
-        // TODO: we need an oracle to fetch the gas price of the current chain
         let gas_price = request.gas_price.unwrap_or_default();
         let max_fee_per_gas = request.max_fee_per_gas.unwrap_or_default();
 
@@ -535,14 +717,14 @@ where
                     from: Some(from),
                     to: request.to,
                     gas: request.gas,
-                    gas_price: Some(U256::from(gas_price)),
-                    max_fee_per_gas: Some(U256::from(max_fee_per_gas)),
+                    gas_price: request.gas_price.map(U256::from),
+                    max_fee_per_gas: request.max_fee_per_gas.map(U256::from),
                     value: request.value,
                     input: request.input.clone().into(),
                     nonce: request.nonce,
                     chain_id: Some(chain_id),
                     access_list: request.access_list.clone(),
-                    max_priority_fee_per_gas: Some(U256::from(max_fee_per_gas)),
+                    max_priority_fee_per_gas: request.max_priority_fee_per_gas.map(U256::from),
                     transaction_type: None,
                     blob_versioned_hashes: None,
                     max_fee_per_blob_gas: None,
@@ -595,6 +777,7 @@ where
 
         // submit the transaction to the pool with a `Local` origin
         let hash = self.pool().add_transaction(TransactionOrigin::Local, pool_transaction).await?;
+        self.record_transaction_arrival(hash, unix_timestamp()).await;
 
         Ok(hash)
     }
@@ -632,6 +815,56 @@ where
             .map_err(|_| EthApiError::InternalBlockingTaskError)?
     }
 
+    async fn spawn_with_call_batch_at<F, R>(
+        &self,
+        requests: Vec<CallRequest>,
+        at: BlockId,
+        overrides: EvmOverrides,
+        f: F,
+    ) -> EthResult<R>
+    where
+        F: FnOnce(StateCacheDB, Vec<ResultAndState>) -> EthResult<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (cfg, block_env, at) = self.evm_env_at(at).await?;
+        let this = self.clone();
+        self.inner
+            .blocking_task_pool
+            .spawn(move || {
+                let state = this.state_at(at)?;
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+                let EvmOverrides { state: mut state_overrides, block: block_overrides } = overrides;
+
+                let mut results = Vec::with_capacity(requests.len());
+                for request in requests {
+                    // state overrides must only be applied once, otherwise they'd reset the
+                    // changes of the previous calls
+                    let overrides =
+                        EvmOverrides::new(state_overrides.take(), block_overrides.clone());
+                    let env = prepare_call_env(
+                        cfg.clone(),
+                        block_env.clone(),
+                        request,
+                        this.call_gas_limit(),
+                        &mut db,
+                        overrides,
+                    )?;
+                    let (res, _) = transact(&mut db, env)?;
+                    db.commit(res.state.clone());
+
+                    let success = res.result.is_success();
+                    results.push(res);
+                    if !success {
+                        break
+                    }
+                }
+                f(db, results)
+            })
+            .await
+            .map_err(|_| EthApiError::InternalBlockingTaskError)?
+    }
+
     async fn transact_call_at(
         &self,
         request: CallRequest,
@@ -658,19 +891,23 @@ where
 
     fn trace_at<F, R>(
         &self,
-        env: Env,
+        mut env: Env,
         config: TracingInspectorConfig,
         at: BlockId,
+        overrides: EvmOverrides,
         f: F,
     ) -> EthResult<R>
     where
         F: FnOnce(TracingInspector, ResultAndState) -> EthResult<R>,
     {
+        let config = self.tracing_limits().apply(config);
         self.with_state_at_block(at, |state| {
-            let db = CacheDB::new(StateProviderDatabase::new(state));
+            let mut db = CacheDB::new(StateProviderDatabase::new(state));
+            apply_evm_overrides(overrides, &mut db, &mut env.block)?;
 
             let mut inspector = TracingInspector::new(config);
             let (res, _) = inspect(db, env, &mut inspector)?;
+            ensure_within_tracing_limits(&inspector)?;
 
             f(inspector, res)
         })
@@ -678,19 +915,24 @@ where
 
     async fn spawn_trace_at_with_state<F, R>(
         &self,
-        env: Env,
+        mut env: Env,
         config: TracingInspectorConfig,
         at: BlockId,
+        overrides: EvmOverrides,
         f: F,
     ) -> EthResult<R>
     where
         F: FnOnce(TracingInspector, ResultAndState, StateCacheDB) -> EthResult<R> + Send + 'static,
         R: Send + 'static,
     {
+        let config = self.tracing_limits().apply(config);
         self.spawn_with_state_at_block(at, move |state| {
-            let db = CacheDB::new(StateProviderDatabase::new(state));
+            let mut db = CacheDB::new(StateProviderDatabase::new(state));
+            apply_evm_overrides(overrides, &mut db, &mut env.block)?;
+
             let mut inspector = TracingInspector::new(config);
             let (res, _, db) = inspect_and_return_db(db, env, &mut inspector)?;
+            ensure_within_tracing_limits(&inspector)?;
 
             f(inspector, res, db)
         })
@@ -727,29 +969,37 @@ where
             + 'static,
         R: Send + 'static,
     {
-        let (transaction, block) = match self.transaction_and_block(hash).await? {
-            None => return Ok(None),
-            Some(res) => res,
+        let Some((transaction, header, block_txs)) =
+            self.transaction_and_recovered_block(hash).await?
+        else {
+            return Ok(None)
         };
         let (tx, tx_info) = transaction.split();
 
-        let (cfg, block_env, _) = self.evm_env_at(block.hash.into()).await?;
+        let (cfg, block_env, _) = self.evm_env_at(header.hash.into()).await?;
+        let config = self.tracing_limits().apply(config);
 
         // we need to get the state of the parent block because we're essentially replaying the
         // block the transaction is included in
-        let parent_block = block.parent_hash;
-        let block_txs = block.body;
+        let parent_block = header.parent_hash;
 
         self.spawn_with_state_at_block(parent_block.into(), move |state| {
             let mut db = CacheDB::new(StateProviderDatabase::new(state));
 
             // replay all transactions prior to the targeted transaction
-            replay_transactions_until(&mut db, cfg.clone(), block_env.clone(), block_txs, tx.hash)?;
+            replay_transactions_until(
+                &mut db,
+                cfg.clone(),
+                block_env.clone(),
+                block_txs.iter().cloned(),
+                tx.hash,
+            )?;
 
             let env = Env { cfg, block: block_env, tx: tx_env_with_recovered(&tx) };
 
             let mut inspector = TracingInspector::new(config);
             let (res, _, db) = inspect_and_return_db(db, env, &mut inspector)?;
+            ensure_within_tracing_limits(&inspector)?;
             f(tx_info, inspector, res, db)
         })
         .await
@@ -797,28 +1047,31 @@ where
             + 'static,
         R: Send + 'static,
     {
-        let ((cfg, block_env, _), block) =
-            futures::try_join!(self.evm_env_at(block_id), self.block_with_senders(block_id))?;
+        let ((cfg, block_env, _), block) = futures::try_join!(
+            self.evm_env_at(block_id),
+            self.block_with_recovered_transactions(block_id)
+        )?;
 
-        let Some(block) = block else { return Ok(None) };
+        let Some((header, block_txs)) = block else { return Ok(None) };
+        let config = self.tracing_limits().apply(config);
 
         // replay all transactions of the block
         self.spawn_tracing_task_with(move |this| {
             // we need to get the state of the parent block because we're replaying this block on
             // top of its parent block's state
-            let state_at = block.parent_hash;
-            let block_hash = block.hash;
+            let state_at = header.parent_hash;
+            let block_hash = header.hash;
 
             let block_number = block_env.number.saturating_to::<u64>();
             let base_fee = block_env.basefee.saturating_to::<u64>();
 
             // prepare transactions, we do everything upfront to reduce time spent with open state
             let max_transactions =
-                highest_index.map_or(block.body.len(), |highest| highest as usize);
+                highest_index.map_or(block_txs.len(), |highest| highest as usize);
             let mut results = Vec::with_capacity(max_transactions);
 
-            let mut transactions = block
-                .into_transactions_ecrecovered()
+            let mut transactions = block_txs
+                .iter()
                 .take(max_transactions)
                 .enumerate()
                 .map(|(idx, tx)| {
@@ -843,6 +1096,7 @@ where
 
                 let mut inspector = TracingInspector::new(config);
                 let (res, _) = inspect(&mut db, env, &mut inspector)?;
+                ensure_within_tracing_limits(&inspector)?;
                 let ResultAndState { result, state } = res;
                 results.push(f(tx_info, inspector, result, &state, &db)?);
 
@@ -860,6 +1114,126 @@ where
         .await
         .map(Some)
     }
+
+    async fn trace_block_until_par<F, R>(
+        &self,
+        block_id: BlockId,
+        highest_index: Option<u64>,
+        config: TracingInspectorConfig,
+        f: F,
+    ) -> EthResult<Option<Vec<R>>>
+    where
+        F: for<'a> Fn(
+                TransactionInfo,
+                TracingInspector,
+                ExecutionResult,
+                &'a State,
+                &'a CacheDB<StateProviderDatabase<StateProviderBox>>,
+            ) -> EthResult<R>
+            + Send
+            + Sync
+            + 'static,
+        R: Send + 'static,
+    {
+        let ((cfg, block_env, _), block) = futures::try_join!(
+            self.evm_env_at(block_id),
+            self.block_with_recovered_transactions(block_id)
+        )?;
+
+        let Some((header, block_txs)) = block else { return Ok(None) };
+        let config = self.tracing_limits().apply(config);
+
+        // we need to get the state of the parent block because we're replaying this block on top
+        // of its parent block's state
+        let state_at: BlockId = header.parent_hash.into();
+        let block_hash = header.hash;
+
+        let block_number = block_env.number.saturating_to::<u64>();
+        let base_fee = block_env.basefee.saturating_to::<u64>();
+
+        let max_transactions = highest_index.map_or(block_txs.len(), |highest| highest as usize);
+        let transactions = block_txs
+            .iter()
+            .take(max_transactions)
+            .enumerate()
+            .map(|(idx, tx)| {
+                let tx_info = TransactionInfo {
+                    hash: Some(tx.hash()),
+                    index: Some(idx as u64),
+                    block_hash: Some(block_hash),
+                    block_number: Some(block_number),
+                    base_fee: Some(base_fee),
+                };
+                (tx_info, tx_env_with_recovered(&tx))
+            })
+            .collect::<Vec<_>>();
+
+        // split the transactions into at most one chunk per thread of the tracing pool, every
+        // chunk is traced sequentially on top of the state right before its first transaction
+        let threads = self.inner.blocking_task_pool.num_threads().max(1);
+        let chunk_size = ((transactions.len() + threads - 1) / threads).max(1);
+        let mut chunks = Vec::with_capacity(threads);
+        let mut transactions = transactions.into_iter().peekable();
+        while transactions.peek().is_some() {
+            chunks.push(transactions.by_ref().take(chunk_size).collect::<Vec<_>>());
+        }
+
+        let f = Arc::new(f);
+        let this = self.clone();
+        let traces = async move {
+            // execute all transactions once to capture the state each chunk is executed on
+            let snapshots = {
+                let (cfg, block_env) = (cfg.clone(), block_env.clone());
+                let chunk_txs = chunks
+                    .iter()
+                    .map(|chunk| chunk.iter().map(|(_, tx)| tx.clone()).collect::<Vec<_>>())
+                    .collect::<Vec<_>>();
+                this.spawn_tracing_task(move |this| {
+                    let state = this.state_at(state_at)?;
+                    let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+                    let mut snapshots = Vec::with_capacity(chunk_txs.len());
+                    for txs in chunk_txs {
+                        snapshots.push(clone_into_empty_db(&db));
+                        for tx in txs {
+                            let env = Env { cfg: cfg.clone(), block: block_env.clone(), tx };
+                            let (ResultAndState { state, .. }, _) = transact(&mut db, env)?;
+                            db.commit(state);
+                        }
+                    }
+                    Ok(snapshots)
+                })
+                .await?
+            };
+
+            let traces = chunks.into_iter().zip(snapshots).map(|(chunk, snapshot)| {
+                let f = Arc::clone(&f);
+                let (cfg, block_env) = (cfg.clone(), block_env.clone());
+                this.spawn_tracing_task(move |this| {
+                    let state = this.state_at(state_at)?;
+                    let mut db = with_cached_state(snapshot, StateProviderDatabase::new(state));
+
+                    let mut results = Vec::with_capacity(chunk.len());
+                    for (tx_info, tx) in chunk {
+                        let env = Env { cfg: cfg.clone(), block: block_env.clone(), tx };
+                        let mut inspector = TracingInspector::new(config);
+                        let (res, _) = inspect(&mut db, env, &mut inspector)?;
+                        ensure_within_tracing_limits(&inspector)?;
+                        let ResultAndState { result, state } = res;
+                        results.push(f(tx_info, inspector, result, &state, &db)?);
+                        db.commit(state);
+                    }
+                    Ok(results)
+                })
+            });
+
+            let traces = futures::future::try_join_all(traces).await?;
+            Ok(traces.into_iter().flatten().collect())
+        };
+
+        // the timeout applies to the entire call rather than to every chunk
+        self.with_tracing_timeout(traces).await.map(Some)
+    }
 }
 
 // === impl EthApi ===
@@ -871,8 +1245,96 @@ where
         BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
     Network: NetworkInfo + Send + Sync + 'static,
 {
+    /// Returns the transaction with the given hash together with the header and the ecrecovered
+    /// transactions of the block it is included in, see [Self::block_with_recovered_transactions].
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    async fn transaction_and_recovered_block(
+        &self,
+        hash: B256,
+    ) -> EthResult<Option<(TransactionSource, SealedHeader, Arc<Vec<TransactionSignedEcRecovered>>)>>
+    {
+        let Some((transaction, block_hash)) = self.historical_transaction_by_hash_at(hash).await?
+        else {
+            return Ok(None)
+        };
+        let block = self.block_with_recovered_transactions(block_hash).await?;
+        Ok(block.map(|(header, transactions)| (transaction, header, transactions)))
+    }
+
+    /// Replays all transactions of the block prior to the mined transaction on top of the parent
+    /// block's state and calls the closure with the resulting state and the [Env] of the
+    /// transaction, on a new task.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    async fn spawn_replay_until<F, R>(&self, hash: B256, f: F) -> EthResult<Option<R>>
+    where
+        F: FnOnce(StateCacheDB, Env) -> EthResult<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let Some((transaction, header, block_txs)) =
+            self.transaction_and_recovered_block(hash).await?
+        else {
+            return Ok(None)
+        };
+        let tx = transaction.into_recovered();
+        let (cfg, block_env, _) = self.evm_env_at(header.hash.into()).await?;
+
+        self.spawn_with_state_at_block(header.parent_hash.into(), move |state| {
+            let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+            // replay all transactions prior to the targeted transaction
+            replay_transactions_until(
+                &mut db,
+                cfg.clone(),
+                block_env.clone(),
+                block_txs.iter().cloned(),
+                tx.hash,
+            )?;
+
+            let env = Env { cfg, block: block_env, tx: tx_env_with_recovered(&tx) };
+            f(db, env)
+        })
+        .await
+        .map(Some)
+    }
+
+    /// Re-executes the mined transaction at its position in the block after applying the given
+    /// modification to its [Env].
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    async fn replay_until_and_transact<F>(
+        &self,
+        hash: B256,
+        env_mutator: F,
+    ) -> EthResult<Option<ResultAndState>>
+    where
+        F: FnOnce(&mut Env) + Send + 'static,
+    {
+        self.spawn_replay_until(hash, move |mut db, mut env| {
+            env_mutator(&mut env);
+            let (res, _) = transact(&mut db, env)?;
+            Ok(res)
+        })
+        .await
+    }
+
     /// Spawns the given closure on a new blocking tracing task
+    ///
+    /// If the task doesn't finish within the configured [TracingLimits::timeout], this returns
+    /// [EthApiError::TracingTimeout]. The task itself is not interrupted, its result is discarded
+    /// once it finishes.
     async fn spawn_tracing_task_with<F, T>(&self, f: F) -> EthResult<T>
+    where
+        F: FnOnce(Self) -> EthResult<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.with_tracing_timeout(self.spawn_tracing_task(f)).await
+    }
+
+    /// Spawns the given closure on a new blocking tracing task without applying the
+    /// [TracingLimits::timeout].
+    async fn spawn_tracing_task<F, T>(&self, f: F) -> EthResult<T>
     where
         F: FnOnce(Self) -> EthResult<T> + Send + 'static,
         T: Send + 'static,
@@ -884,443 +1346,5435 @@ where
             .await
             .map_err(|_| EthApiError::InternalBlockingTaskError)?
     }
-}
 
-impl<Provider, Pool, Network> EthApi<Provider, Pool, Network>
-where
-    Provider:
-        BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
-    Network: NetworkInfo + 'static,
-{
-    /// Helper function for `eth_getTransactionReceipt`
+    /// Awaits the given future, returning [EthApiError::TracingTimeout] if it doesn't finish
+    /// within the configured [TracingLimits::timeout].
+    async fn with_tracing_timeout<T>(
+        &self,
+        fut: impl Future<Output = EthResult<T>>,
+    ) -> EthResult<T> {
+        match self.tracing_limits().timeout {
+            Some(timeout) => tokio::time::timeout(timeout, fut)
+                .await
+                .map_err(|_| EthApiError::TracingTimeout(timeout))?,
+            None => fut.await,
+        }
+    }
+
+    /// Looks up the 4-byte function selector of the transaction in the given set of known
+    /// selectors.
     ///
-    /// Returns the receipt
-    #[cfg(not(feature = "optimism"))]
-    pub(crate) async fn build_transaction_receipt(
+    /// Returns the label of the matching selector, or `Some(None)` if the selector is not part of
+    /// the set.
+    ///
+    /// Returns `None` if the transaction does not exist or its input is shorter than a selector.
+    pub async fn transaction_known_selector(
         &self,
-        tx: TransactionSigned,
-        meta: TransactionMeta,
-        receipt: Receipt,
-    ) -> EthResult<TransactionReceipt> {
-        // get all receipts for the block
-        let all_receipts = match self.cache().get_receipts(meta.block_hash).await? {
-            Some(recpts) => recpts,
-            None => return Err(EthApiError::UnknownBlockNumber),
+        hash: B256,
+        selectors: HashMap<[u8; 4], String>,
+    ) -> EthResult<Option<Option<String>>> {
+        let Some(tx) = self.transaction_by_hash(hash).await? else { return Ok(None) };
+        let tx = tx.into_recovered();
+        let Some(selector) = function_selector(tx.input()) else { return Ok(None) };
+        Ok(Some(selectors.get(&selector).cloned()))
+    }
+
+    /// Returns the receipts of the transactions right before and after the given transaction in
+    /// its block.
+    ///
+    /// Returns `None` if the transaction does not exist or is pending.
+    pub async fn adjacent_receipts(&self, hash: B256) -> EthResult<Option<AdjacentReceipts>> {
+        let Some(TransactionSource::Block { index, block_hash, .. }) =
+            self.transaction_by_hash(hash).await?
+        else {
+            return Ok(None)
         };
-        build_transaction_receipt_with_block_receipts(tx, meta, receipt, &all_receipts)
+        let Some(receipts) = self.block_receipts(block_hash.into()).await? else { return Ok(None) };
+
+        let index = index as usize;
+        let previous = index.checked_sub(1).and_then(|idx| receipts.get(idx).cloned());
+        let next = receipts.get(index + 1).cloned();
+        Ok(Some(AdjacentReceipts { previous, next }))
     }
 
-    /// Helper function for `eth_getTransactionReceipt` (optimism)
+    /// Returns the number of seconds between the arrival of the transaction in the pool and the
+    /// timestamp of the block it was mined in.
     ///
-    /// Returns the receipt
-    #[cfg(feature = "optimism")]
-    pub(crate) async fn build_transaction_receipt(
-        &self,
-        tx: TransactionSigned,
-        meta: TransactionMeta,
-        receipt: Receipt,
-    ) -> EthResult<TransactionReceipt> {
-        let (block, receipts) = self
-            .cache()
-            .get_block_and_receipts(meta.block_hash)
-            .await?
-            .ok_or(EthApiError::UnknownBlockNumber)?;
+    /// Only the arrival of transactions that were submitted through this API is tracked.
+    ///
+    /// Returns `None` if the arrival time is unknown or the transaction is not mined yet.
+    pub async fn inclusion_latency(&self, hash: B256) -> EthResult<Option<u64>> {
+        let Some(arrival) = self.transaction_arrival(hash).await else { return Ok(None) };
+        let Some(TransactionSource::Block { block_hash, .. }) =
+            self.transaction_by_hash(hash).await?
+        else {
+            return Ok(None)
+        };
+        let Some(header) = self.provider().header(&block_hash)? else { return Ok(None) };
+        Ok(Some(header.timestamp.saturating_sub(arrival)))
+    }
 
-        let block = block.unseal();
-        let l1_block_info = reth_revm::optimism::extract_l1_info(&block).ok();
-        let optimism_tx_meta = self.build_op_tx_meta(&tx, l1_block_info, block.timestamp)?;
+    /// Returns whether the two transactions touch overlapping state, which means they can't be
+    /// safely executed in parallel.
+    ///
+    /// Both transactions are executed independently on top of the state before the earlier of
+    /// their blocks. The transactions conflict if one of them writes an account or storage slot the
+    /// other one reads or writes. Fee payments to the block's beneficiary are ignored.
+    ///
+    /// Returns `None` if one of the transactions does not exist or is not mined yet.
+    pub async fn spawn_transactions_conflict(&self, a: B256, b: B256) -> EthResult<Option<bool>> {
+        let (Some((tx_a, block_a)), Some((tx_b, block_b))) =
+            futures::try_join!(self.transaction_and_block(a), self.transaction_and_block(b))?
+        else {
+            return Ok(None)
+        };
 
-        build_transaction_receipt_with_block_receipts(
-            tx,
-            meta,
-            receipt,
-            &receipts,
-            optimism_tx_meta,
-        )
+        let base = if block_a.number <= block_b.number { block_a } else { block_b };
+        let (cfg, block_env, _) = self.evm_env_at(base.hash.into()).await?;
+        let (tx_a, tx_b) = (tx_a.into_recovered(), tx_b.into_recovered());
+
+        self.spawn_with_state_at_block(base.parent_hash.into(), move |state| {
+            let access = |tx: &TransactionSignedEcRecovered| {
+                let mut env = Env {
+                    cfg: cfg.clone(),
+                    block: block_env.clone(),
+                    tx: tx_env_with_recovered(tx),
+                };
+                // the transactions are not necessarily executed in their original order
+                env.tx.nonce = None;
+                AccessSet::of_transaction(&state, env)
+            };
+            Ok(access(&tx_a)?.conflicts_with(&access(&tx_b)?))
+        })
+        .await
+        .map(Some)
     }
 
-    /// Builds [OptimismTxMeta] object using the provided [TransactionSigned],
-    /// [L1BlockInfo] and `block_timestamp`. The [L1BlockInfo] is used to calculate
-    /// the l1 fee and l1 data gas for the transaction.
-    /// If the [L1BlockInfo] is not provided, the [OptimismTxMeta] will be empty.
-    #[cfg(feature = "optimism")]
-    pub(crate) fn build_op_tx_meta(
-        &self,
-        tx: &TransactionSigned,
-        l1_block_info: Option<L1BlockInfo>,
-        block_timestamp: u64,
-    ) -> EthResult<OptimismTxMeta> {
-        if let Some(l1_block_info) = l1_block_info {
-            let envelope_buf: Bytes = {
-                let mut envelope_buf = bytes::BytesMut::default();
-                tx.encode_enveloped(&mut envelope_buf);
-                envelope_buf.freeze().into()
-            };
+    /// Returns the percentile rank of the transaction's effective gas price among the effective gas
+    /// prices of all transactions in its block.
+    ///
+    /// The rank is the percentage of transactions in the block paying at most the same gas price,
+    /// so the highest priced transaction is at the 100th percentile.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn gas_price_percentile_in_block(&self, hash: B256) -> EthResult<Option<f64>> {
+        let Some((tx, block)) = self.transaction_and_block(hash).await? else { return Ok(None) };
+        let gas_price = U256::from(tx.into_recovered().effective_gas_price(block.base_fee_per_gas));
+        let Some(prices) = self.block_effective_gas_prices(block.hash.into()).await? else {
+            return Ok(None)
+        };
+        let at_most = prices.iter().filter(|price| **price <= gas_price).count();
+        Ok(Some(at_most as f64 / prices.len() as f64 * 100.0))
+    }
 
-            let (l1_fee, l1_data_gas) = match (!tx.is_deposit())
-                .then(|| {
-                    let inner_l1_fee = match l1_block_info.l1_tx_data_fee(
-                        &self.inner.provider.chain_spec(),
-                        block_timestamp,
-                        &envelope_buf,
-                        tx.is_deposit(),
-                    ) {
-                        Ok(inner_l1_fee) => inner_l1_fee,
-                        Err(e) => return Err(e),
-                    };
-                    let inner_l1_data_gas = match l1_block_info.l1_data_gas(
-                        &self.inner.provider.chain_spec(),
-                        block_timestamp,
-                        &envelope_buf,
-                    ) {
-                        Ok(inner_l1_data_gas) => inner_l1_data_gas,
-                        Err(e) => return Err(e),
-                    };
-                    Ok((inner_l1_fee, inner_l1_data_gas))
+    /// Returns the total number of call frames executed by the transaction, including the top
+    /// level call.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn spawn_call_count(&self, hash: B256) -> EthResult<Option<usize>> {
+        self.spawn_trace_transaction_in_block(
+            hash,
+            TracingInspectorConfig::default_parity(),
+            |_, inspector, _, _| Ok(inspector.get_traces().nodes().len()),
+        )
+        .await
+    }
+
+    /// Traces the transaction and returns the destination and the remaining gas of every `JUMP`
+    /// and `JUMPI` executed by the transaction, in execution order.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn spawn_jump_gas_profile(&self, hash: B256) -> EthResult<Option<Vec<JumpPoint>>> {
+        let config = TracingInspectorConfig::default_parity()
+            .set_steps(true)
+            .set_stack_snapshots(StackSnapshotType::Full);
+        self.spawn_trace_transaction_in_block(hash, config, |_, inspector, _, _| {
+            let jumps = inspector
+                .get_traces()
+                .nodes()
+                .iter()
+                .flat_map(|node| node.trace.steps.iter())
+                .filter(|step| matches!(step.op.get(), opcode::JUMP | opcode::JUMPI))
+                .filter_map(|step| {
+                    let destination = step.stack.as_ref()?.last()?;
+                    Some(JumpPoint {
+                        contract: step.contract,
+                        pc: step.pc,
+                        destination: destination.saturating_to(),
+                        gas_remaining: step.gas_remaining,
+                    })
                 })
-                .transpose()
-                .map_err(|_| EthApiError::InternalEthError)?
-            {
-                Some((l1_fee, l1_data_gas)) => (Some(l1_fee), Some(l1_data_gas)),
-                None => (None, None),
-            };
+                .collect();
+            Ok(jumps)
+        })
+        .await
+    }
 
-            Ok(OptimismTxMeta::new(Some(l1_block_info), l1_fee, l1_data_gas))
-        } else {
-            Ok(OptimismTxMeta::default())
+    /// Returns whether the mined transaction succeeded and, if it failed, the decoded revert
+    /// reason.
+    ///
+    /// The revert reason is not part of the receipt, so failed transactions are re-executed at
+    /// their position in the block to obtain the revert data.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn transaction_outcome(&self, hash: B256) -> EthResult<Option<TxOutcome>> {
+        let Some(receipt) = self.provider().receipt_by_hash(hash)? else { return Ok(None) };
+        if receipt.success {
+            return Ok(Some(TxOutcome { success: true, revert_reason: None }))
         }
+
+        let revert_reason = self
+            .spawn_trace_transaction_in_block(
+                hash,
+                TracingInspectorConfig::default_parity(),
+                |_, _, res, _| match res.result {
+                    ExecutionResult::Revert { output, .. } => Ok(decode_revert_output(&output)),
+                    _ => Ok(None),
+                },
+            )
+            .await?
+            .flatten();
+        Ok(Some(TxOutcome { success: false, revert_reason }))
     }
 
-    /// Helper function for `eth_sendRawTransaction` for Optimism.
+    /// Returns the tip the transaction pays per gas and the currently suggested tip of the gas
+    /// price oracle.
     ///
-    /// Forwards the raw transaction bytes to the configured sequencer endpoint.
-    /// This is a no-op if the sequencer endpoint is not configured.
-    #[cfg(feature = "optimism")]
-    pub async fn forward_to_sequencer(&self, tx: &Bytes) -> EthResult<()> {
-        if let Some(endpoint) = self.network().sequencer_endpoint() {
-            let body = serde_json::to_string(&serde_json::json!({
-                "jsonrpc": "2.0",
-                "method": "eth_sendRawTransaction",
-                "params": [format!("0x{}", alloy_primitives::hex::encode(tx))],
-                "id": self.network().chain_id()
-            }))
-            .map_err(|_| {
-                tracing::warn!(
-                    target = "rpc::eth",
-                    "Failed to serialize transaction for forwarding to sequencer"
-                );
-                EthApiError::InternalEthError
-            })?;
+    /// For mined transactions this is the effective tip at the base fee of their block, for
+    /// pending transactions the max priority fee, or the gas price for legacy transactions.
+    ///
+    /// Returns `None` if the transaction does not exist.
+    pub async fn tip_vs_suggestion(&self, hash: B256) -> EthResult<Option<(U256, U256)>> {
+        let Some(tx) = self.transaction_by_hash(hash).await? else { return Ok(None) };
+        let tip = match tx {
+            TransactionSource::Pool(tx) => tx.priority_fee_or_price(),
+            TransactionSource::Block { transaction, base_fee, .. } => {
+                transaction.effective_tip_per_gas(base_fee).unwrap_or_default()
+            }
+        };
+        let suggested = self.suggested_priority_fee().await?;
+        Ok(Some((U256::from(tip), suggested)))
+    }
 
-            self.inner
-                .http_client
-                .post(endpoint)
-                .header(http::header::CONTENT_TYPE, "application/json")
-                .body(body)
-                .send()
-                .await
-                .map_err(|_| EthApiError::InternalEthError)?;
+    /// Returns the sum of the gas limits of all pending transactions in the pool.
+    ///
+    /// Compared to the block gas limit, this indicates how many blocks' worth of work is queued.
+    pub fn pool_pending_gas(&self) -> EthResult<u64> {
+        Ok(self.pool().pending_transactions().iter().map(|tx| tx.gas_limit()).sum())
+    }
+
+    /// Traces the transaction and returns the addresses whose code was inspected via
+    /// `EXTCODESIZE`, `EXTCODEHASH` or `EXTCODECOPY`, in order of their first access.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn spawn_external_code_reads(&self, hash: B256) -> EthResult<Option<Vec<Address>>> {
+        let config = TracingInspectorConfig::default_parity()
+            .set_steps(true)
+            .set_stack_snapshots(StackSnapshotType::Full);
+        self.spawn_trace_transaction_in_block(hash, config, |_, inspector, _, _| {
+            let mut seen = HashSet::new();
+            let reads = inspector
+                .get_traces()
+                .nodes()
+                .iter()
+                .flat_map(|node| node.trace.steps.iter())
+                .filter(|step| {
+                    matches!(
+                        step.op.get(),
+                        opcode::EXTCODESIZE | opcode::EXTCODEHASH | opcode::EXTCODECOPY
+                    )
+                })
+                .filter_map(|step| step.stack.as_ref()?.last().copied())
+                .map(|word| Address::from_word(B256::from(word.to_be_bytes())))
+                .filter(|address| seen.insert(*address))
+                .collect();
+            Ok(reads)
+        })
+        .await
+    }
+
+    /// Returns the fee the mined transaction paid, alongside its effective gas price and the gas
+    /// it used.
+    ///
+    /// On optimism the total fee includes the L1 data fee of the transaction.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn transaction_fee(&self, hash: B256) -> EthResult<Option<TxFee>> {
+        let Some(receipt) = self.transaction_receipt(hash).await? else { return Ok(None) };
+        let gas_used = receipt.gas_used.unwrap_or_default().saturating_to::<u64>();
+        let effective_gas_price = U256::from(receipt.effective_gas_price.to::<u128>());
+
+        #[allow(unused_mut)]
+        let mut total_fee_wei = effective_gas_price * U256::from(gas_used);
+        #[cfg(feature = "optimism")]
+        {
+            total_fee_wei += receipt.l1_fee.unwrap_or_default();
         }
-        Ok(())
+
+        Ok(Some(TxFee { total_fee_wei, effective_gas_price, gas_used }))
     }
-}
 
-impl<Provider, Pool, Network> EthApi<Provider, Pool, Network>
-where
-    Pool: TransactionPool + 'static,
-    Provider:
-        BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
-    Network: NetworkInfo + Send + Sync + 'static,
-{
-    pub(crate) fn sign_request(
-        &self,
-        from: &Address,
-        request: TypedTransactionRequest,
-    ) -> EthResult<TransactionSigned> {
-        for signer in self.inner.signers.iter() {
-            if signer.is_signer_for(from) {
-                return match signer.sign_transaction(request, from) {
-                    Ok(tx) => Ok(tx),
-                    Err(e) => Err(e.into()),
-                }
+    /// Returns the pending transactions of the pool that would be included in the next block, in
+    /// the order of the pool's best transactions.
+    ///
+    /// Transactions are selected until the gas limit of the pending block is reached. A
+    /// transaction that no longer fits is skipped together with its descendants.
+    pub fn next_block_transactions(&self) -> EthResult<Vec<Transaction>> {
+        let pending = self.pending_block_env_and_cfg()?;
+        let header = pending.origin.header();
+        let block_gas_limit = header.gas_limit;
+
+        let mut best_txs = self
+            .pool()
+            .best_transactions_with_base_fee(header.base_fee_per_gas.unwrap_or_default());
+        let mut cumulative_gas_used = 0;
+        let mut selected = Vec::new();
+        while let Some(pool_tx) = best_txs.next() {
+            if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
+                best_txs.mark_invalid(&pool_tx);
+                continue
             }
+            cumulative_gas_used += pool_tx.gas_limit();
+            selected.push(from_recovered(pool_tx.to_recovered_transaction()));
         }
-        Err(EthApiError::InvalidTransactionSignature)
+
+        Ok(selected)
     }
 
-    /// Get Transaction by [BlockId] and the index of the transaction within that Block.
+    /// Traces the transaction and returns the total gas spent on `LOG0` to `LOG4` operations,
+    /// including the costs for topics, data and memory expansion.
     ///
-    /// Returns `Ok(None)` if the block does not exist, or the block as fewer transactions
-    pub(crate) async fn transaction_by_block_and_tx_index(
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn spawn_log_gas(&self, hash: B256) -> EthResult<Option<u64>> {
+        self.spawn_trace_transaction_in_block(
+            hash,
+            TracingInspectorConfig::default_parity().set_steps(true),
+            |_, inspector, _, _| {
+                Ok(inspector
+                    .get_traces()
+                    .nodes()
+                    .iter()
+                    .flat_map(|node| node.trace.steps.iter())
+                    .filter(|step| (opcode::LOG0..=opcode::LOG4).contains(&step.op.get()))
+                    .map(|step| step.gas_cost)
+                    .sum())
+            },
+        )
+        .await
+    }
+
+    /// Returns the Shannon entropy of the transaction's calldata in bits per byte.
+    ///
+    /// The entropy ranges from `0.0` for empty or uniform data to `8.0` for random data, so low
+    /// values indicate highly compressible calldata.
+    ///
+    /// Returns `None` if the transaction does not exist.
+    pub async fn transaction_input_entropy(&self, hash: B256) -> EthResult<Option<f64>> {
+        let Some(tx) = self.transaction_by_hash(hash).await? else { return Ok(None) };
+        Ok(Some(shannon_entropy(tx.into_recovered().input())))
+    }
+
+    /// Returns the number of the pending block the pool transaction would be included in.
+    ///
+    /// Returns `None` if the transaction does not exist or is already mined.
+    pub async fn pending_transaction_target_block(&self, hash: B256) -> EthResult<Option<u64>> {
+        if self.pool().get(&hash).is_none() {
+            return Ok(None)
+        }
+        Ok(Some(self.pending_block_env_and_cfg()?.origin.header().number))
+    }
+
+    /// Re-executes the mined transaction at its position in the block and returns, per contract,
+    /// the storage slots the transaction changed with their values before and after the
+    /// transaction.
+    ///
+    /// Slots that were written with their current value are not included.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn spawn_persisted_storage_diff(
         &self,
-        block_id: impl Into<BlockId>,
-        index: Index,
-    ) -> EthResult<Option<Transaction>> {
-        if let Some(block) = self.block_with_senders(block_id.into()).await? {
-            let block_hash = block.hash;
-            let block_number = block.number;
-            let base_fee_per_gas = block.base_fee_per_gas;
-            if let Some(tx) = block.into_transactions_ecrecovered().nth(index.into()) {
-                return Ok(Some(from_recovered_with_block_context(
-                    tx,
-                    block_hash,
-                    block_number,
-                    base_fee_per_gas,
-                    index.into(),
-                )))
+        hash: B256,
+    ) -> EthResult<Option<HashMap<Address, HashMap<B256, (B256, B256)>>>> {
+        self.spawn_trace_transaction_in_block(
+            hash,
+            TracingInspectorConfig::default_parity(),
+            |_, _, res, _| {
+                let diff = res
+                    .state
+                    .into_iter()
+                    .filter_map(|(address, account)| {
+                        let slots = account
+                            .storage
+                            .into_iter()
+                            .filter(|(_, value)| value.is_changed())
+                            .map(|(slot, value)| {
+                                let change = (
+                                    value.previous_or_original_value.into(),
+                                    value.present_value.into(),
+                                );
+                                (slot.into(), change)
+                            })
+                            .collect::<HashMap<B256, (B256, B256)>>();
+                        (!slots.is_empty()).then_some((address, slots))
+                    })
+                    .collect();
+                Ok(diff)
+            },
+        )
+        .await
+    }
+
+    /// Heuristically checks whether the pending transaction could be profitably front-run.
+    ///
+    /// The transaction is executed on top of the pending state and considered an opportunity if it
+    /// succeeds, calls into other contracts and either uses a well known DEX swap selector or
+    /// moves at least 10 ether through its internal calls.
+    ///
+    /// Note: this is a heuristic, it neither detects every opportunity nor guarantees that a
+    /// reported opportunity is actually profitable.
+    ///
+    /// Returns `None` if the transaction does not exist or is already mined.
+    pub async fn spawn_frontrun_opportunity(&self, hash: B256) -> EthResult<Option<bool>> {
+        let Some(TransactionSource::Pool(tx)) = self.transaction_by_hash(hash).await? else {
+            return Ok(None)
+        };
+        let is_swap = function_selector(tx.input())
+            .is_some_and(|selector| KNOWN_SWAP_SELECTORS.contains(&selector));
+        let (cfg, block_env, at) = self.evm_env_at(BlockNumberOrTag::Pending.into()).await?;
+
+        self.spawn_with_state_at_block(at, move |state| {
+            let db = CacheDB::new(StateProviderDatabase::new(state));
+            let env = Env { cfg, block: block_env, tx: tx_env_with_recovered(&tx) };
+            let mut inspector = TracingInspector::new(TracingInspectorConfig::default_parity());
+            let (res, _) = inspect(db, env, &mut inspector)?;
+            if !res.result.is_success() {
+                return Ok(false)
             }
-        }
 
-        Ok(None)
+            // the first node is the top level call
+            let internal_calls = inspector.get_traces().nodes().iter().skip(1);
+            let mut has_internal_calls = false;
+            let mut moved_value = U256::ZERO;
+            for node in internal_calls {
+                has_internal_calls = true;
+                moved_value = moved_value.saturating_add(node.trace.value);
+            }
+
+            Ok(has_internal_calls && (is_swap || moved_value >= FRONTRUN_VALUE_THRESHOLD))
+        })
+        .await
+        .map(Some)
     }
-}
-/// Represents from where a transaction was fetched.
-#[derive(Debug, Clone, Eq, PartialEq)]
-pub enum TransactionSource {
-    /// Transaction exists in the pool (Pending)
-    Pool(TransactionSignedEcRecovered),
-    /// Transaction already included in a block
+
+    /// Returns the intrinsic gas charged for the transaction's calldata.
     ///
-    /// This can be a historical block or a pending block (received from the CL)
-    Block {
-        /// Transaction fetched via provider
-        transaction: TransactionSignedEcRecovered,
-        /// Index of the transaction in the block
-        index: u64,
-        /// Hash of the block.
-        block_hash: B256,
-        /// Number of the block.
-        block_number: u64,
-        /// base fee of the block.
-        base_fee: Option<u64>,
-    },
-}
+    /// Zero bytes cost 4 gas, non-zero bytes cost 16 gas since Istanbul (EIP-2028) and 68 gas
+    /// before. Mined transactions use the spec of their block, pending transactions the spec of the
+    /// next block.
+    ///
+    /// Returns `None` if the transaction does not exist.
+    pub async fn calldata_gas_cost(&self, hash: B256) -> EthResult<Option<u64>> {
+        let Some(tx) = self.transaction_by_hash(hash).await? else { return Ok(None) };
+        let block_number = match &tx {
+            TransactionSource::Block { block_number, .. } => *block_number,
+            TransactionSource::Pool(_) => self.provider().best_block_number()? + 1,
+        };
+        let non_zero_byte_cost = if self
+            .provider()
+            .chain_spec()
+            .fork(Hardfork::Istanbul)
+            .active_at_block(block_number)
+        {
+            16
+        } else {
+            68
+        };
 
-// === impl TransactionSource ===
+        let cost = tx
+            .into_recovered()
+            .input()
+            .iter()
+            .map(|byte| if *byte == 0 { 4 } else { non_zero_byte_cost })
+            .sum();
+        Ok(Some(cost))
+    }
 
-impl TransactionSource {
-    /// Consumes the type and returns the wrapped transaction.
-    pub fn into_recovered(self) -> TransactionSignedEcRecovered {
-        self.into()
+    /// Returns the length of the init code of a contract creation transaction.
+    ///
+    /// Returns `None` if the transaction does not exist or doesn't create a contract.
+    pub async fn creation_init_code_size(&self, hash: B256) -> EthResult<Option<usize>> {
+        let Some(tx) = self.transaction_by_hash(hash).await? else { return Ok(None) };
+        let tx = tx.into_recovered();
+        Ok(tx.kind().is_create().then(|| tx.input().len()))
     }
 
-    /// Returns the transaction and block related info, if not pending
-    pub fn split(self) -> (TransactionSignedEcRecovered, TransactionInfo) {
-        match self {
-            TransactionSource::Pool(tx) => {
-                let hash = tx.hash();
-                (
-                    tx,
-                    TransactionInfo {
-                        hash: Some(hash),
-                        index: None,
-                        block_hash: None,
-                        block_number: None,
-                        base_fee: None,
-                    },
-                )
-            }
-            TransactionSource::Block { transaction, index, block_hash, block_number, base_fee } => {
-                let hash = transaction.hash();
-                (
-                    transaction,
-                    TransactionInfo {
-                        hash: Some(hash),
-                        index: Some(index),
-                        block_hash: Some(block_hash),
-                        block_number: Some(block_number),
-                        base_fee,
-                    },
-                )
+    /// Re-executes the mined transaction at its position in the block with the given gas limit.
+    ///
+    /// This can be used to test whether a transaction behaves differently under constrained gas,
+    /// for example to check a contract for gas-griefing vulnerabilities.
+    pub async fn call_with_gas_limit_at(
+        &self,
+        hash: B256,
+        gas_limit: u64,
+    ) -> EthResult<ResultAndState> {
+        self.replay_until_and_transact(hash, move |env| env.tx.gas_limit = gas_limit)
+            .await?
+            .ok_or(EthApiError::TransactionNotFound)
+    }
+
+    /// Re-executes the mined transaction at its position in the block with the given value.
+    ///
+    /// The sender, calldata and gas limit stay unchanged. This can be used to test how a contract
+    /// reacts to different amounts of ETH sent to it.
+    pub async fn call_with_modified_value_at(
+        &self,
+        hash: B256,
+        new_value: U256,
+    ) -> EthResult<ResultAndState> {
+        self.replay_until_and_transact(hash, move |env| env.tx.value = new_value)
+            .await?
+            .ok_or(EthApiError::TransactionNotFound)
+    }
+
+    /// Traces the transaction and returns the block numbers it requested via `BLOCKHASH`, in
+    /// execution order.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn spawn_blockhash_reads(&self, hash: B256) -> EthResult<Option<Vec<u64>>> {
+        let config = TracingInspectorConfig::default_parity()
+            .set_steps(true)
+            .set_stack_snapshots(StackSnapshotType::Full);
+        self.spawn_trace_transaction_in_block(hash, config, |_, inspector, _, _| {
+            Ok(inspector
+                .get_traces()
+                .nodes()
+                .iter()
+                .flat_map(|node| node.trace.steps.iter())
+                .filter(|step| step.op.get() == opcode::BLOCKHASH)
+                .filter_map(|step| step.stack.as_ref()?.last().map(|number| number.saturating_to()))
+                .collect())
+        })
+        .await
+    }
+
+    /// Returns the number of pending pool transactions per gas price bucket.
+    ///
+    /// The buckets are the ascending lower bounds of the gas price tiers: a transaction falls into
+    /// the last bucket whose bound is at most its effective gas price at the pending base fee.
+    /// Transactions priced below the first bound are not counted.
+    pub fn pool_gas_price_histogram(&self, buckets: Vec<U256>) -> EthResult<Vec<usize>> {
+        let base_fee = self.pool().block_info().pending_basefee;
+        let mut counts = vec![0; buckets.len()];
+        for tx in self.pool().pending_transactions() {
+            let price =
+                U256::from(tx.to_recovered_transaction().effective_gas_price(Some(base_fee)));
+            let bucket = buckets.partition_point(|bound| *bound <= price);
+            if let Some(count) = bucket.checked_sub(1).and_then(|idx| counts.get_mut(idx)) {
+                *count += 1;
             }
         }
+        Ok(counts)
+    }
+
+    /// Re-executes the mined transaction at its position in the block and returns the balance of
+    /// the given account before and after the transaction.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn spawn_balance_change_for(
+        &self,
+        hash: B256,
+        account: Address,
+    ) -> EthResult<Option<(U256, U256)>> {
+        self.spawn_trace_transaction_in_block(
+            hash,
+            TracingInspectorConfig::default_parity(),
+            move |_, _, res, db| {
+                let before = db.basic_ref(account)?.map(|acc| acc.balance).unwrap_or_default();
+                let after = res.state.get(&account).map_or(before, |acc| acc.info.balance);
+                Ok((before, after))
+            },
+        )
+        .await
+    }
+
+    /// Returns the position of the transaction among the transactions of the same sender in its
+    /// block and the number of transactions the sender has in the block.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn sender_tx_position_in_block(
+        &self,
+        hash: B256,
+    ) -> EthResult<Option<(usize, usize)>> {
+        let Some((tx, _, block_txs)) = self.transaction_and_recovered_block(hash).await? else {
+            return Ok(None)
+        };
+        let sender = tx.into_recovered().signer();
+
+        let mut position = None;
+        let mut total = 0;
+        for tx in block_txs.iter().filter(|tx| tx.signer() == sender) {
+            if tx.hash == hash {
+                position = Some(total);
+            }
+            total += 1;
+        }
+        Ok(position.map(|position| (position, total)))
+    }
+
+    /// Returns the minimum balance the sender needed to execute the mined transaction.
+    ///
+    /// The transaction is re-executed at its position in the block and the minimum is what it
+    /// actually spent: the transferred value plus the gas used at the effective gas price and, for
+    /// blob transactions, the blob gas at the block's blob gas price.
+    ///
+    /// Note: the EVM's upfront balance check uses the gas limit at the max fee per gas instead, so
+    /// the sender may need more than this to get the transaction accepted.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn spawn_minimum_balance_required(&self, hash: B256) -> EthResult<Option<U256>> {
+        let Some(TransactionSource::Block { transaction: tx, block_hash, base_fee, .. }) =
+            self.transaction_by_hash(hash).await?
+        else {
+            return Ok(None)
+        };
+        let blob_fee = self.provider().header(&block_hash)?.and_then(|header| header.blob_fee());
+
+        self.spawn_trace_transaction_in_block(
+            hash,
+            TracingInspectorConfig::default_parity(),
+            move |_, _, res, _| {
+                let value: U256 = tx.value().into();
+                let gas_cost = U256::from(res.result.gas_used()) *
+                    U256::from(tx.effective_gas_price(base_fee));
+                let blob_cost = U256::from(tx.blob_gas_used().unwrap_or_default()) *
+                    U256::from(blob_fee.unwrap_or_default());
+                Ok(value + gas_cost + blob_cost)
+            },
+        )
+        .await
+    }
+
+    /// Returns whether the mined transaction called into a contract that was deployed by an earlier
+    /// transaction of the same block.
+    ///
+    /// This traces all transactions of the block up to and including the given transaction.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn spawn_interacts_with_same_block_deploy(
+        &self,
+        hash: B256,
+    ) -> EthResult<Option<bool>> {
+        let Some(TransactionSource::Block { index, block_hash, .. }) =
+            self.transaction_by_hash(hash).await?
+        else {
+            return Ok(None)
+        };
+
+        let Some(accesses) = self
+            .trace_block_until(
+                block_hash.into(),
+                Some(index + 1),
+                TracingInspectorConfig::default_parity(),
+                |_, inspector, _, _, _| {
+                    let mut created = HashSet::new();
+                    let mut called = HashSet::new();
+                    for node in inspector.get_traces().nodes().iter().filter(|n| n.trace.success) {
+                        if node.trace.kind.is_any_create() {
+                            created.insert(node.trace.address);
+                        } else {
+                            called.insert(node.trace.address);
+                        }
+                    }
+                    Ok((created, called))
+                },
+            )
+            .await?
+        else {
+            return Ok(None)
+        };
+
+        let Some(((_, called), earlier)) = accesses.split_last() else { return Ok(None) };
+        Ok(Some(earlier.iter().any(|(created, _)| !created.is_disjoint(called))))
+    }
+
+    /// Re-executes the mined transaction at its position in the block and returns the largest
+    /// memory size in bytes any of its call frames expanded to.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn spawn_memory_high_water_mark(&self, hash: B256) -> EthResult<Option<u64>> {
+        self.spawn_replay_until(hash, move |mut db, env| {
+            let mut inspector = MemoryHighWaterMarkInspector::default();
+            inspect(&mut db, env, &mut inspector)?;
+            Ok(inspector.high_water_mark as u64)
+        })
+        .await
+    }
+
+    /// Re-executes the mined transaction at its position in the block and returns the accounts
+    /// and storage slots it read and wrote.
+    ///
+    /// The access sets of two transactions can be compared with [AccessSet::conflicts_with] to
+    /// build a dependency graph for parallel execution.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn spawn_transaction_access_set(&self, hash: B256) -> EthResult<Option<AccessSet>> {
+        self.spawn_replay_until(hash, AccessSet::of_transaction_with_db).await
+    }
+
+    /// Returns `true` if the nonce of the mined transaction is the nonce the sender was expected
+    /// to use at that point of the block.
+    ///
+    /// The expected nonce is the sender's nonce at the parent block plus the number of
+    /// transactions the sender has earlier in the same block.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn verify_transaction_nonce(&self, hash: B256) -> EthResult<Option<bool>> {
+        let Some((tx, header, block_txs)) = self.transaction_and_recovered_block(hash).await?
+        else {
+            return Ok(None)
+        };
+        let tx = tx.into_recovered();
+        let sender = tx.signer();
+        let earlier = block_txs
+            .iter()
+            .take_while(|tx| tx.hash != hash)
+            .filter(|tx| tx.signer() == sender)
+            .count() as u64;
+
+        let nonce = self
+            .spawn_with_state_at_block(header.parent_hash.into(), move |state| {
+                Ok(state.account_nonce(sender)?.unwrap_or_default())
+            })
+            .await?;
+        Ok(Some(tx.nonce() == nonce + earlier))
+    }
+
+    /// Traces the transaction and returns the addresses of the call frames the revert bubbled up
+    /// through, starting with the innermost reverting frame and ending with the top level call.
+    ///
+    /// Where a failed frame made several failed calls, the revert is assumed to originate in the
+    /// last one. Returns an empty path if the transaction succeeded, and `None` if the
+    /// transaction does not exist or is not mined yet.
+    pub async fn spawn_revert_bubble_path(&self, hash: B256) -> EthResult<Option<Vec<Address>>> {
+        self.spawn_trace_transaction_in_block(
+            hash,
+            TracingInspectorConfig::default_parity(),
+            |_, inspector, _, _| {
+                let nodes = inspector.get_traces().nodes();
+                let Some(mut node) = nodes.first().filter(|node| !node.trace.success) else {
+                    return Ok(Vec::new())
+                };
+
+                // descend into the frame the revert originated in
+                while let Some(&child) =
+                    node.children.iter().rev().find(|&&child| !nodes[child].trace.success)
+                {
+                    node = &nodes[child];
+                }
+
+                let mut path = vec![node.trace.address];
+                while let Some(parent) = node.parent {
+                    node = &nodes[parent];
+                    path.push(node.trace.address);
+                }
+                Ok(path)
+            },
+        )
+        .await
+    }
+
+    /// Returns `true` if the transaction read or wrote the given storage slot of the contract.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn spawn_touched_slot(
+        &self,
+        hash: B256,
+        contract: Address,
+        slot: B256,
+    ) -> EthResult<Option<bool>> {
+        let slot = U256::from_be_bytes(slot.0);
+        self.spawn_trace_transaction_in_block(
+            hash,
+            TracingInspectorConfig::default_parity(),
+            move |_, _, res, _| {
+                Ok(res
+                    .state
+                    .get(&contract)
+                    .is_some_and(|account| account.storage.contains_key(&slot)))
+            },
+        )
+        .await
+    }
+
+    /// Returns the number of distinct accounts the mined transaction called, read or wrote,
+    /// including its sender.
+    ///
+    /// The block's beneficiary is not counted, see [AccessSet].
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn spawn_interaction_count(&self, hash: B256) -> EthResult<Option<usize>> {
+        let Some(access) = self.spawn_transaction_access_set(hash).await? else { return Ok(None) };
+        // written state is always part of the read set
+        let accounts = access.reads.iter().map(|(address, _)| address).collect::<HashSet<_>>();
+        Ok(Some(accounts.len()))
+    }
+
+    /// Returns the ratio of the effective gas price the mined transaction paid to the base fee of
+    /// its block.
+    ///
+    /// Returns `None` if the transaction does not exist, is not mined yet or its block has no base
+    /// fee.
+    pub async fn gas_price_base_fee_ratio(&self, hash: B256) -> EthResult<Option<f64>> {
+        let Some(TransactionSource::Block { transaction, base_fee, .. }) =
+            self.transaction_by_hash(hash).await?
+        else {
+            return Ok(None)
+        };
+        let Some(base_fee) = base_fee.filter(|base_fee| *base_fee > 0) else { return Ok(None) };
+        let effective_gas_price = transaction.effective_gas_price(Some(base_fee));
+        Ok(Some(effective_gas_price as f64 / base_fee as f64))
+    }
+
+    /// Returns the status of the transaction with the given hash.
+    ///
+    /// Mined transactions are looked up on disk first. Transactions in the pool are classified as
+    /// [TransactionStatus::Pending] if the pool holds a transaction of the sender for every nonce
+    /// between the sender's latest nonce and the transaction's nonce, or
+    /// [TransactionStatus::Queued] if there's a nonce gap.
+    pub async fn transaction_status(&self, hash: B256) -> EthResult<TransactionStatus> {
+        let status = match self.transaction_by_hash(hash).await? {
+            None => TransactionStatus::Unknown,
+            Some(TransactionSource::Block { block_hash, block_number, index, .. }) => {
+                TransactionStatus::Mined { block_hash, block_number, index }
+            }
+            Some(TransactionSource::Pool(tx)) => {
+                let sender = tx.signer();
+                let latest_nonce =
+                    self.provider().latest()?.account_nonce(sender)?.unwrap_or_default();
+                let ancestors = self
+                    .pool()
+                    .get_transactions_by_sender(sender)
+                    .iter()
+                    .filter(|pooled| (latest_nonce..tx.nonce()).contains(&pooled.nonce()))
+                    .count() as u64;
+                if ancestors >= tx.nonce().saturating_sub(latest_nonce) {
+                    TransactionStatus::Pending
+                } else {
+                    TransactionStatus::Queued
+                }
+            }
+        };
+        Ok(status)
+    }
+
+    /// Returns the deepest call stack depth reached while executing the transaction.
+    ///
+    /// The top-level call has depth `0`, every nested call or create adds one level. This can be
+    /// used to detect recursion close to the EVM's call depth limit of `1024`.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn spawn_max_call_depth(&self, hash: B256) -> EthResult<Option<u64>> {
+        self.spawn_trace_transaction_in_block(
+            hash,
+            TracingInspectorConfig::default_parity(),
+            |_, inspector, _, _| {
+                let nodes = inspector.get_traces().nodes();
+                Ok(nodes.iter().map(|node| node.trace.depth as u64).max().unwrap_or_default())
+            },
+        )
+        .await
+    }
+
+    /// Re-executes the transaction with the given hash on top of the latest state and returns
+    /// whether it still succeeds.
+    ///
+    /// This works for both pending and mined transactions and can be used to detect whether a
+    /// transaction was invalidated by state changes since it was submitted or simulated. The nonce
+    /// is not checked, because it's already consumed if the transaction is mined, or may be ahead
+    /// of the sender's nonce if the transaction is queued.
+    ///
+    /// Returns `None` if the transaction does not exist.
+    pub async fn call_still_valid_at_latest(&self, hash: B256) -> EthResult<Option<bool>> {
+        let Some(tx) = self.transaction_by_hash(hash).await? else { return Ok(None) };
+        let tx = tx.into_recovered();
+        let (cfg, block_env, at) = self.evm_env_at(BlockNumberOrTag::Latest.into()).await?;
+
+        self.spawn_with_state_at_block(at, move |state| {
+            let mut db = CacheDB::new(StateProviderDatabase::new(state));
+            let mut env = Env { cfg, block: block_env, tx: tx_env_with_recovered(&tx) };
+            env.tx.nonce = None;
+            match transact(&mut db, env) {
+                Ok((res, _)) => Ok(Some(res.result.is_success())),
+                Err(EthApiError::InvalidTransaction(_)) => Ok(Some(false)),
+                Err(err) => Err(err),
+            }
+        })
+        .await
+    }
+
+    /// Fills in the fees the request omits with suggestions from the gas oracle.
+    ///
+    /// Requests with a gas price are left unchanged. EIP-2930 requests, and requests without any
+    /// fee fields if the pending block has no base fee because London is not active yet, get the
+    /// suggested gas price. For all other requests the suggested priority fee is used, and the max
+    /// fee is set to `2 * base fee + priority fee` of the pending block, so that the transaction
+    /// stays includable if the base fee rises.
+    pub(crate) async fn fill_missing_fees(
+        &self,
+        request: &mut TransactionRequest,
+    ) -> EthResult<()> {
+        if request.gas_price.is_some() {
+            return Ok(())
+        }
+
+        let has_dynamic_fees = request.max_fee_per_gas.is_some() ||
+            request.max_priority_fee_per_gas.is_some() ||
+            request.max_fee_per_blob_gas.is_some() ||
+            request.blob_versioned_hashes.is_some() ||
+            request.sidecar.is_some();
+        let base_fee = if has_dynamic_fees {
+            Some(self.pending_block_env_and_cfg()?.block_env.basefee)
+        } else if request.access_list.is_some() {
+            // EIP-2930 request
+            None
+        } else {
+            // converted into a legacy request if the chain doesn't support EIP-1559 yet
+            self.pending_block_env_and_cfg()?.origin.header().base_fee_per_gas.map(U256::from)
+        };
+
+        let Some(base_fee) = base_fee else {
+            let gas_price = self.gas_price().await?;
+            request.gas_price = Some(U128::from(gas_price.saturating_to::<u128>()));
+            return Ok(())
+        };
+
+        let priority_fee = match request.max_priority_fee_per_gas {
+            Some(priority_fee) => U256::from(priority_fee),
+            None => self.suggested_priority_fee().await?,
+        };
+        request.max_priority_fee_per_gas = Some(U128::from(priority_fee.saturating_to::<u128>()));
+        if request.max_fee_per_gas.is_none() {
+            let max_fee = base_fee.saturating_mul(U256::from(2)).saturating_add(priority_fee);
+            request.max_fee_per_gas = Some(U128::from(max_fee.saturating_to::<u128>()));
+        }
+        Ok(())
+    }
+
+    /// Returns the intrinsic gas charged for the access list declared by the transaction.
+    ///
+    /// Every address in the list costs `2400` gas and every storage key `1900` gas, see EIP-2930.
+    /// Transactions without an access list return `0`.
+    ///
+    /// Returns `None` if the transaction does not exist.
+    pub async fn access_list_gas(&self, hash: B256) -> EthResult<Option<u64>> {
+        let Some(tx) = self.transaction_by_hash(hash).await? else { return Ok(None) };
+        let gas = tx
+            .into_recovered()
+            .access_list()
+            .map(|access_list| {
+                access_list
+                    .0
+                    .iter()
+                    .map(|item| {
+                        ACCESS_LIST_ADDRESS +
+                            ACCESS_LIST_STORAGE_KEY * item.storage_keys.len() as u64
+                    })
+                    .sum()
+            })
+            .unwrap_or_default();
+        Ok(Some(gas))
+    }
+
+    /// Re-executes the mined transaction at its position in the block, once with its declared
+    /// access list and once without an access list, and returns the gas used by both executions as
+    /// `(with_access_list, without_access_list)`.
+    ///
+    /// This measures how much gas the transaction's access list actually saves or costs.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn compare_access_list_gas(&self, hash: B256) -> EthResult<Option<(u64, u64)>> {
+        self.spawn_replay_until(hash, move |mut db, env| {
+            let (res, mut env) = transact(&mut db, env)?;
+            let with_access_list = res.result.gas_used();
+
+            env.tx.access_list.clear();
+            let (res, _) = transact(&mut db, env)?;
+            Ok((with_access_list, res.result.gas_used()))
+        })
+        .await
+    }
+
+    /// Returns the gas refund the mined transaction accrued during execution, before the refund
+    /// cap of `gas_used / 5` (`gas_used / 2` before London) was applied.
+    ///
+    /// Comparing this with the refund that was actually applied shows how much refund was lost to
+    /// the cap.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn spawn_max_possible_refund(&self, hash: B256) -> EthResult<Option<u64>> {
+        self.spawn_replay_until(hash, move |mut db, env| {
+            let mut inspector = RefundInspector::default();
+            inspect(&mut db, env, &mut inspector)?;
+            Ok(inspector.refund.max(0) as u64)
+        })
+        .await
+    }
+
+    /// Returns the gas the mined transaction spent on EVM execution, that is the gas used by the
+    /// transaction minus its intrinsic gas.
+    ///
+    /// The intrinsic gas is calculated with the post-merge rules, see
+    /// [calculate_intrinsic_gas_after_merge].
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn execution_gas_used(&self, hash: B256) -> EthResult<Option<u64>> {
+        let (transaction, receipt) =
+            futures::try_join!(self.transaction_and_block(hash), self.transaction_receipt(hash))?;
+        let (Some((tx, block)), Some(receipt)) = (transaction, receipt) else { return Ok(None) };
+
+        let tx = tx.into_recovered();
+        let is_shanghai =
+            self.provider().chain_spec().is_shanghai_active_at_timestamp(block.timestamp);
+        let access_list = tx.access_list().map(|list| list.flattened()).unwrap_or_default();
+        let intrinsic_gas =
+            calculate_intrinsic_gas_after_merge(tx.input(), tx.kind(), &access_list, is_shanghai);
+
+        let gas_used = receipt.gas_used.unwrap_or_default().saturating_to::<u64>();
+        Ok(Some(gas_used.saturating_sub(intrinsic_gas)))
+    }
+
+    /// Returns the geth `prestateTracer` output for the transaction with the given hash.
+    ///
+    /// In default mode this returns the state of every account touched by the transaction as it
+    /// was right before the transaction. In `diff_mode` this returns separate `pre` and `post` maps
+    /// of the changed accounts, where newly created accounts are omitted from `pre` and
+    /// self-destructed accounts are omitted from `post`.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn trace_transaction_prestate(
+        &self,
+        hash: B256,
+        diff_mode: bool,
+    ) -> EthResult<Option<PreStateFrame>> {
+        let prestate_config = PreStateConfig { diff_mode: Some(diff_mode) };
+        // in default mode all touched storage slots are returned, which requires recording steps
+        // and state diffs
+        let config = TracingInspectorConfig::default_geth()
+            .set_memory_snapshots(false)
+            .set_stack_snapshots(StackSnapshotType::None)
+            .set_steps_and_state_diffs(prestate_config.is_default_mode());
+
+        self.spawn_trace_transaction_in_block(hash, config, move |_, inspector, res, db| {
+            Ok(inspector.into_geth_builder().geth_prestate_traces(&res, prestate_config, &db)?)
+        })
+        .await
+    }
+
+    /// Returns the geth `callTracer` output for the transaction with the given hash.
+    ///
+    /// The returned [CallFrame] is the root of the call tree, with nested calls in
+    /// [CallFrame::calls]. Failed frames carry an error and, if the call reverted with
+    /// `Error(string)`, the decoded revert reason.
+    ///
+    /// If `only_top_call` is set, nested calls are omitted. If `with_logs` is set, emitted logs are
+    /// attached to the frame that emitted them.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn trace_transaction_call_frame(
+        &self,
+        hash: B256,
+        with_logs: bool,
+        only_top_call: bool,
+    ) -> EthResult<Option<CallFrame>> {
+        let call_config =
+            CallConfig { only_top_call: Some(only_top_call), with_log: Some(with_logs) };
+        // the call tree is derived from the recorded calls, so no opcode level steps are needed
+        let config = TracingInspectorConfig::default_geth()
+            .set_steps_and_state_diffs(false)
+            .set_memory_snapshots(false)
+            .set_stack_snapshots(StackSnapshotType::None)
+            .set_record_logs(with_logs);
+
+        self.spawn_trace_transaction_in_block(hash, config, move |_, inspector, res, _| {
+            Ok(inspector.into_geth_builder().geth_call_traces(call_config, res.result.gas_used()))
+        })
+        .await
+    }
+
+    /// Submits the raw transactions to the pool in a single batch and returns the result of every
+    /// transaction in the order they were given.
+    ///
+    /// All transactions are recovered first, then the valid ones are added to the pool at once so
+    /// that they are validated and inserted in a single pass. A transaction that fails to decode or
+    /// is rejected by the pool only fails its own result.
+    ///
+    /// On optimism, the recovered transactions are forwarded to the sequencer in a single batch
+    /// request, and transactions rejected by the sequencer are not added to the pool.
+    pub async fn send_raw_transactions(
+        &self,
+        txs: Vec<Bytes>,
+        origin: TransactionOrigin,
+    ) -> EthResult<Vec<EthResult<B256>>> {
+        // every slot is overwritten with the transaction's outcome below
+        let mut results: Vec<EthResult<B256>> =
+            (0..txs.len()).map(|_| Err(EthApiError::InternalEthError)).collect();
+        let mut recovered = Vec::with_capacity(txs.len());
+        for (idx, tx) in txs.into_iter().enumerate() {
+            match recover_raw_transaction(tx.clone()) {
+                Ok(transaction) => recovered.push((idx, tx, transaction)),
+                Err(err) => results[idx] = Err(err),
+            }
+        }
+
+        // On optimism, transactions are forwarded directly to the sequencer to be included in
+        // blocks that it builds.
+        #[cfg(feature = "optimism")]
+        let recovered = {
+            let raw = recovered.iter().map(|(_, tx, _)| tx.clone()).collect::<Vec<_>>();
+            let forwarded = self.forward_batch_to_sequencer(&raw).await?;
+            let mut accepted = Vec::with_capacity(recovered.len());
+            for (entry, res) in recovered.into_iter().zip(forwarded) {
+                match res {
+                    Ok(()) => accepted.push(entry),
+                    Err(err) => results[entry.0] = Err(err),
+                }
+            }
+            accepted
+        };
+
+        let (indices, pool_transactions): (Vec<_>, Vec<_>) = recovered
+            .into_iter()
+            .map(|(idx, _, transaction)| {
+                (idx, <Pool::Transaction>::from_recovered_pooled_transaction(transaction))
+            })
+            .unzip();
+
+        if !pool_transactions.is_empty() {
+            let outcomes = self.pool().add_transactions(origin, pool_transactions).await?;
+            let now = unix_timestamp();
+            for (idx, outcome) in indices.into_iter().zip(outcomes) {
+                if let Ok(hash) = outcome {
+                    self.record_transaction_arrival(hash, now).await;
+                }
+                results[idx] = outcome.map_err(Into::into);
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Checks whether the raw transaction could be included in the next block on top of the
+    /// latest state, without submitting it to the pool.
+    ///
+    /// The nonce, the sender's balance, the intrinsic gas and the fee cap are checked against the
+    /// latest block and its state, see [EligibilityReport].
+    pub async fn inclusion_eligibility(&self, tx: Bytes) -> EthResult<EligibilityReport> {
+        let tx = recover_raw_transaction(tx)?.into_ecrecovered_transaction();
+        let header = self.provider().latest_header()?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let account =
+            self.state_at(header.hash.into())?.basic_account(tx.signer())?.unwrap_or_default();
+
+        let mut max_cost = U256::from(tx.gas_limit())
+            .saturating_mul(U256::from(tx.max_fee_per_gas()))
+            .saturating_add(tx.value().into());
+        if let (Some(blob_gas), Some(blob_fee)) = (tx.blob_gas_used(), tx.max_fee_per_blob_gas()) {
+            max_cost = max_cost.saturating_add(U256::from(blob_gas) * U256::from(blob_fee));
+        }
+
+        let is_shanghai =
+            self.provider().chain_spec().is_shanghai_active_at_timestamp(header.timestamp);
+        let access_list = tx.access_list().map(|list| list.flattened()).unwrap_or_default();
+        let intrinsic_gas =
+            calculate_intrinsic_gas_after_merge(tx.input(), tx.kind(), &access_list, is_shanghai);
+
+        Ok(EligibilityReport {
+            nonce_too_low: tx.nonce() < account.nonce,
+            nonce_too_high: tx.nonce() > account.nonce,
+            insufficient_balance: account.balance < max_cost,
+            intrinsic_gas_too_low: tx.gas_limit() < intrinsic_gas,
+            fee_cap_too_low: tx.max_fee_per_gas() <
+                header.base_fee_per_gas.unwrap_or_default() as u128,
+        })
+    }
+
+    /// Returns the fee the mined transaction paid per byte of calldata, that is
+    /// `gas_used * effective_gas_price / calldata_length` in wei.
+    ///
+    /// Returns `None` if the transaction does not exist, is not mined yet or has no calldata.
+    pub async fn gas_per_calldata_byte(&self, hash: B256) -> EthResult<Option<f64>> {
+        let (transaction, receipt) =
+            futures::try_join!(self.transaction_by_hash(hash), self.transaction_receipt(hash))?;
+        let (Some(tx), Some(receipt)) = (transaction, receipt) else { return Ok(None) };
+
+        let calldata_len = tx.into_recovered().input().len();
+        if calldata_len == 0 {
+            return Ok(None)
+        }
+        let gas_used = receipt.gas_used.unwrap_or_default().saturating_to::<u64>();
+        let fee = gas_used as f64 * receipt.effective_gas_price.to::<u128>() as f64;
+        Ok(Some(fee / calldata_len as f64))
+    }
+
+    /// Returns the global index of the mined transaction, that is the number of transactions in
+    /// all canonical blocks before it, counted from genesis.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn global_transaction_index(&self, hash: B256) -> EthResult<Option<u64>> {
+        Ok(self.provider().transaction_id(hash)?)
+    }
+
+    /// Returns the transaction the given sender sent with the given nonce.
+    ///
+    /// Only pending and queued transactions in the pool are searched, because the provider does
+    /// not index mined transactions by sender and nonce. Returns `None` if the pool does not
+    /// contain a matching transaction, even if it was already mined.
+    pub async fn transaction_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> EthResult<Option<TransactionSource>> {
+        Ok(self
+            .pool()
+            .get_transactions_by_sender(sender)
+            .into_iter()
+            .find(|tx| tx.nonce() == nonce)
+            .map(|tx| TransactionSource::Pool(tx.transaction.to_recovered_transaction())))
+    }
+
+    /// Returns the ratio of the mined transaction's encoded envelope size in bytes to the gas it
+    /// used.
+    ///
+    /// A high ratio indicates a data heavy transaction, a low ratio a compute heavy one.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn size_to_gas_ratio(&self, hash: B256) -> EthResult<Option<f64>> {
+        let (transaction, receipt) =
+            futures::try_join!(self.transaction_by_hash(hash), self.transaction_receipt(hash))?;
+        let (Some(tx), Some(receipt)) = (transaction, receipt) else { return Ok(None) };
+
+        let size = tx.into_recovered().envelope_encoded().len();
+        let gas_used = receipt.gas_used.unwrap_or_default().saturating_to::<u64>();
+        if gas_used == 0 {
+            return Ok(None)
+        }
+        Ok(Some(size as f64 / gas_used as f64))
+    }
+
+    /// Executes the call request at the given [BlockId] like [EthTransactions::transact_call_at]
+    /// and additionally returns the fees of the call, see [CallFees].
+    pub async fn transact_call_with_fees_at(
+        &self,
+        request: CallRequest,
+        at: BlockId,
+        overrides: EvmOverrides,
+    ) -> EthResult<(ResultAndState, Env, CallFees)> {
+        let (res, env) = self.transact_call_at(request, at, overrides).await?;
+
+        let effective_gas_price =
+            env.tx.gas_priority_fee.map_or(env.tx.gas_price, |priority_fee| {
+                env.tx.gas_price.min(env.block.basefee.saturating_add(priority_fee))
+            });
+        let kind = match env.tx.transact_to {
+            TransactTo::Call(to) => Call(to),
+            TransactTo::Create(_) => Create,
+        };
+        let intrinsic_gas =
+            calculate_intrinsic_gas(&env.tx.data, &kind, &env.tx.access_list, env.cfg.spec_id);
+        let gas_refunded = match res.result {
+            ExecutionResult::Success { gas_refunded, .. } => gas_refunded,
+            _ => 0,
+        };
+
+        let fees = CallFees {
+            effective_gas_price,
+            gas_used: res.result.gas_used(),
+            gas_refunded,
+            intrinsic_gas,
+        };
+        Ok((res, env, fees))
+    }
+
+    /// Returns the effective gas prices of the first `n` of the pool's best pending transactions,
+    /// in the order of the pool's best transactions.
+    ///
+    /// The effective gas prices are calculated against the pending base fee of the pool.
+    pub fn pool_top_gas_prices(&self, n: usize) -> EthResult<Vec<U256>> {
+        let base_fee = self.pool().block_info().pending_basefee;
+        Ok(self
+            .pool()
+            .best_transactions_with_base_fee(base_fee)
+            .take(n)
+            .map(|tx| U256::from(tx.to_recovered_transaction().effective_gas_price(Some(base_fee))))
+            .collect())
+    }
+
+    /// Returns whether the mined transaction's gas used is within `tolerance_pct` percent of its
+    /// gas limit, which indicates a well estimated gas limit.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn is_gas_tight(&self, hash: B256, tolerance_pct: f64) -> EthResult<Option<bool>> {
+        let (transaction, receipt) =
+            futures::try_join!(self.transaction_by_hash(hash), self.transaction_receipt(hash))?;
+        let (Some(tx), Some(receipt)) = (transaction, receipt) else { return Ok(None) };
+
+        let gas_limit = tx.into_recovered().gas_limit();
+        let gas_used = receipt.gas_used.unwrap_or_default().saturating_to::<u64>();
+        let unused = gas_limit.saturating_sub(gas_used) as f64;
+        Ok(Some(unused <= gas_limit as f64 * tolerance_pct / 100.0))
+    }
+
+    /// Traces the transaction and returns which instructions of the given contract's code were
+    /// executed, see [CoverageReport].
+    ///
+    /// Only steps executed in the context of the contract are taken into account, and the code is
+    /// the contract's code before the transaction.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn spawn_opcode_coverage(
+        &self,
+        hash: B256,
+        contract: Address,
+    ) -> EthResult<Option<CoverageReport>> {
+        let config = TracingInspectorConfig::default_parity().set_steps(true);
+        self.spawn_trace_transaction_in_block(hash, config, move |_, inspector, _, db| {
+            let code = match db.basic_ref(contract)? {
+                Some(account) => db.code_by_hash_ref(account.code_hash)?.original_bytes(),
+                None => Bytes::new(),
+            };
+            let executed = inspector
+                .get_traces()
+                .nodes()
+                .iter()
+                .flat_map(|node| node.trace.steps.iter())
+                .filter(|step| step.contract == contract)
+                .map(|step| step.pc)
+                .collect::<HashSet<_>>();
+
+            let mut report = CoverageReport::default();
+            let mut pc = 0;
+            while pc < code.len() {
+                if executed.contains(&pc) {
+                    report.covered.push(pc);
+                } else {
+                    report.uncovered.push(pc);
+                }
+                // skip the immediate bytes of PUSH instructions
+                let op = code[pc];
+                pc += 1;
+                if (opcode::PUSH1..=opcode::PUSH32).contains(&op) {
+                    pc += (op - opcode::PUSH1 + 1) as usize;
+                }
+            }
+            Ok(report)
+        })
+        .await
+    }
+
+    /// Re-executes the mined transaction at its position in the block and estimates the net
+    /// growth of the state in bytes.
+    ///
+    /// Every storage slot set from zero adds [STORAGE_SLOT_STATE_BYTES], every slot cleared to zero
+    /// removes them. Every new account adds [ACCOUNT_STATE_BYTES] plus the size of its deployed
+    /// code, every removed account removes [ACCOUNT_STATE_BYTES]. The growth is negative if the
+    /// transaction clears more state than it creates.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    pub async fn spawn_state_growth(&self, hash: B256) -> EthResult<Option<i64>> {
+        self.spawn_trace_transaction_in_block(
+            hash,
+            TracingInspectorConfig::default_parity(),
+            |_, _, res, db| {
+                let mut growth = 0i64;
+                for (address, account) in res.state {
+                    let existed = db.basic_ref(address)?.is_some_and(|info| !info.is_empty());
+                    if account.is_selfdestructed() {
+                        if existed {
+                            growth -= ACCOUNT_STATE_BYTES;
+                        }
+                        continue
+                    }
+                    if !existed && !account.info.is_empty() {
+                        let code_len = account
+                            .info
+                            .code
+                            .as_ref()
+                            .map_or(0, |code| code.original_bytes().len());
+                        growth += ACCOUNT_STATE_BYTES + code_len as i64;
+                    }
+                    for slot in account.storage.values().filter(|slot| slot.is_changed()) {
+                        if slot.previous_or_original_value.is_zero() {
+                            growth += STORAGE_SLOT_STATE_BYTES;
+                        } else if slot.present_value.is_zero() {
+                            growth -= STORAGE_SLOT_STATE_BYTES;
+                        }
+                    }
+                }
+                Ok(growth)
+            },
+        )
+        .await
+    }
+
+    /// Executes the call request on top of the state at the given [BlockId] under the rules of
+    /// the given fork.
+    ///
+    /// This can be used to test how a contract behaves under the rules of a future hardfork.
+    pub async fn call_under_spec_at(
+        &self,
+        request: CallRequest,
+        spec: SpecId,
+        at: BlockId,
+        overrides: EvmOverrides,
+    ) -> EthResult<ResultAndState> {
+        self.spawn_with_call_at(request, at, overrides, move |mut db, mut env| {
+            env.cfg.spec_id = spec;
+            let (res, _) = transact(&mut db, env)?;
+            Ok(res)
+        })
+        .await
+    }
+
+    /// Executes the calls of the bundle in order on top of the state at the given [BlockId] and
+    /// traces every call with a fresh [TracingInspector].
+    ///
+    /// The state changes of every call are committed before the next call is executed. Unlike
+    /// [EthTransactions::spawn_with_call_batch_at], a call that reverts or halts doesn't stop the
+    /// bundle, its result is returned and execution continues with the next call. The state
+    /// overrides are applied once before the first call, the block overrides apply to every call.
+    pub async fn trace_call_many(
+        &self,
+        bundle: Vec<CallRequest>,
+        at: BlockId,
+        overrides: EvmOverrides,
+        config: TracingInspectorConfig,
+    ) -> EthResult<Vec<(TracingInspector, ResultAndState)>> {
+        let (cfg, block_env, at) = self.evm_env_at(at).await?;
+        let config = self.tracing_limits().apply(config);
+
+        self.spawn_tracing_task_with(move |this| {
+            let state = this.state_at(at)?;
+            let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+            let EvmOverrides { state: mut state_overrides, block: block_overrides } = overrides;
+
+            let mut results = Vec::with_capacity(bundle.len());
+            for request in bundle {
+                // state overrides must only be applied once, otherwise they'd reset the changes of
+                // the previous calls
+                let overrides = EvmOverrides::new(state_overrides.take(), block_overrides.clone());
+                let env = prepare_call_env(
+                    cfg.clone(),
+                    block_env.clone(),
+                    request,
+                    this.call_gas_limit(),
+                    &mut db,
+                    overrides,
+                )?;
+
+                let mut inspector = TracingInspector::new(config);
+                let (res, _) = inspect(&mut db, env, &mut inspector)?;
+                ensure_within_tracing_limits(&inspector)?;
+                db.commit(res.state.clone());
+                results.push((inspector, res));
+            }
+            Ok(results)
+        })
+        .await
+    }
+
+    /// Returns whether the success of the pool transaction depends on the transactions selected
+    /// into the pending block before it.
+    ///
+    /// The prior transactions are selected from the pool's best transactions like in
+    /// [Self::next_block_transactions]. The transaction is executed on top of the state the pending
+    /// block is built on, once without and once with the prior transactions applied. It depends on
+    /// them if only one of the executions succeeds. If the transaction would not be selected at
+    /// all, all selected transactions are applied.
+    ///
+    /// Returns `None` if the transaction is not in the pool.
+    pub async fn spawn_pending_dependency(&self, hash: B256) -> EthResult<Option<bool>> {
+        let Some(pool_tx) = self.pool().get(&hash) else { return Ok(None) };
+        let tx = pool_tx.transaction.to_recovered_transaction();
+
+        let PendingBlockEnv { cfg, block_env, origin } = self.pending_block_env_and_cfg()?;
+        let header = origin.header();
+        let block_gas_limit = header.gas_limit;
+
+        let mut best_txs = self
+            .pool()
+            .best_transactions_with_base_fee(header.base_fee_per_gas.unwrap_or_default());
+        let mut cumulative_gas_used = 0;
+        let mut prior_txs = Vec::new();
+        while let Some(pool_tx) = best_txs.next() {
+            if *pool_tx.hash() == hash {
+                break
+            }
+            if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
+                best_txs.mark_invalid(&pool_tx);
+                continue
+            }
+            cumulative_gas_used += pool_tx.gas_limit();
+            prior_txs.push(pool_tx.to_recovered_transaction());
+        }
+
+        self.spawn_with_state_at_block(origin.build_target_hash().into(), move |state| {
+            let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+            // transactions that are invalid on top of the state are considered failed
+            let succeeds = |db: &mut StateCacheDB, env: Env| match transact(db, env) {
+                Ok((res, _)) => Ok(res.result.is_success()),
+                Err(EthApiError::InvalidTransaction(_)) => Ok(false),
+                Err(err) => Err(err),
+            };
+
+            let env =
+                Env { cfg: cfg.clone(), block: block_env.clone(), tx: tx_env_with_recovered(&tx) };
+            let without_prior = succeeds(&mut db, env.clone())?;
+
+            for prior_tx in prior_txs {
+                let env = Env {
+                    cfg: cfg.clone(),
+                    block: block_env.clone(),
+                    tx: tx_env_with_recovered(&prior_tx),
+                };
+                match transact(&mut db, env) {
+                    Ok((res, _)) => db.commit(res.state),
+                    // invalid transactions would not be included
+                    Err(EthApiError::InvalidTransaction(_)) => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            let with_prior = succeeds(&mut db, env)?;
+
+            Ok(without_prior != with_prior)
+        })
+        .await
+        .map(Some)
+    }
+
+    /// Returns `true` if the call reads any block context that varies between blocks, like
+    /// `PREVRANDAO`, `BLOCKHASH` or `TIMESTAMP`.
+    ///
+    /// If this returns `false`, the result of the call does not depend on the block it is executed
+    /// in, only on the state.
+    pub async fn call_uses_randomness_at(
+        &self,
+        request: CallRequest,
+        at: BlockId,
+    ) -> EthResult<bool> {
+        self.spawn_with_call_at(request, at, EvmOverrides::default(), |db, env| {
+            let mut inspector =
+                TracingInspector::new(TracingInspectorConfig::default_parity().set_steps(true));
+            inspect(db, env, &mut inspector)?;
+            Ok(inspector
+                .get_traces()
+                .nodes()
+                .iter()
+                .flat_map(|node| node.trace.steps.iter())
+                .any(|step| is_block_context_opcode(step.op.get())))
+        })
+        .await
+    }
+}
+
+impl<Provider, Pool, Network> EthApi<Provider, Pool, Network>
+where
+    Provider:
+        BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Network: NetworkInfo + 'static,
+{
+    /// Helper function for `eth_getTransactionReceipt`
+    ///
+    /// Returns the receipt
+    #[cfg(not(feature = "optimism"))]
+    pub(crate) async fn build_transaction_receipt(
+        &self,
+        tx: TransactionSigned,
+        meta: TransactionMeta,
+        receipt: Receipt,
+    ) -> EthResult<TransactionReceipt> {
+        // get all receipts for the block
+        let all_receipts = match self.cache().get_receipts(meta.block_hash).await? {
+            Some(recpts) => recpts,
+            None => return Err(EthApiError::UnknownBlockNumber),
+        };
+        build_transaction_receipt_with_block_receipts(tx, meta, receipt, &all_receipts)
+    }
+
+    /// Helper function for `eth_getTransactionReceipt` (optimism)
+    ///
+    /// Returns the receipt
+    #[cfg(feature = "optimism")]
+    pub(crate) async fn build_transaction_receipt(
+        &self,
+        tx: TransactionSigned,
+        meta: TransactionMeta,
+        receipt: Receipt,
+    ) -> EthResult<TransactionReceipt> {
+        let (block, receipts) = self
+            .cache()
+            .get_block_and_receipts(meta.block_hash)
+            .await?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+
+        let block = block.unseal();
+        let l1_block_info = reth_revm::optimism::extract_l1_info(&block).ok();
+        let optimism_tx_meta = self.build_op_tx_meta(&tx, l1_block_info, block.timestamp)?;
+
+        build_transaction_receipt_with_block_receipts(
+            tx,
+            meta,
+            receipt,
+            &receipts,
+            optimism_tx_meta,
+        )
+    }
+
+    /// Builds [OptimismTxMeta] object using the provided [TransactionSigned],
+    /// [L1BlockInfo] and `block_timestamp`. The [L1BlockInfo] is used to calculate
+    /// the l1 fee and l1 data gas for the transaction.
+    /// If the [L1BlockInfo] is not provided, the [OptimismTxMeta] will be empty.
+    #[cfg(feature = "optimism")]
+    pub(crate) fn build_op_tx_meta(
+        &self,
+        tx: &TransactionSigned,
+        l1_block_info: Option<L1BlockInfo>,
+        block_timestamp: u64,
+    ) -> EthResult<OptimismTxMeta> {
+        if let Some(l1_block_info) = l1_block_info {
+            let envelope_buf: Bytes = {
+                let mut envelope_buf = bytes::BytesMut::default();
+                tx.encode_enveloped(&mut envelope_buf);
+                envelope_buf.freeze().into()
+            };
+
+            let (l1_fee, l1_data_gas) = match (!tx.is_deposit())
+                .then(|| {
+                    let inner_l1_fee = match l1_block_info.l1_tx_data_fee(
+                        &self.inner.provider.chain_spec(),
+                        block_timestamp,
+                        &envelope_buf,
+                        tx.is_deposit(),
+                    ) {
+                        Ok(inner_l1_fee) => inner_l1_fee,
+                        Err(e) => return Err(e),
+                    };
+                    let inner_l1_data_gas = match l1_block_info.l1_data_gas(
+                        &self.inner.provider.chain_spec(),
+                        block_timestamp,
+                        &envelope_buf,
+                    ) {
+                        Ok(inner_l1_data_gas) => inner_l1_data_gas,
+                        Err(e) => return Err(e),
+                    };
+                    Ok((inner_l1_fee, inner_l1_data_gas))
+                })
+                .transpose()
+                .map_err(|_| EthApiError::InternalEthError)?
+            {
+                Some((l1_fee, l1_data_gas)) => (Some(l1_fee), Some(l1_data_gas)),
+                None => (None, None),
+            };
+
+            Ok(OptimismTxMeta::new(Some(l1_block_info), l1_fee, l1_data_gas))
+        } else {
+            Ok(OptimismTxMeta::default())
+        }
+    }
+
+    /// Returns the ratio of the compressed size of the transaction to its enveloped size, as
+    /// estimated by the L1 data fee model of the block the transaction was included in.
+    ///
+    /// Returns `None` if the transaction does not exist or is not mined yet.
+    #[cfg(feature = "optimism")]
+    pub async fn calldata_compression_ratio(&self, hash: B256) -> EthResult<Option<f64>> {
+        let Some((tx, block)) = self.transaction_and_block(hash).await? else { return Ok(None) };
+        let block = block.unseal();
+        let l1_block_info = reth_revm::optimism::extract_l1_info(&block)
+            .map_err(|_| EthApiError::InternalEthError)?;
+        let envelope = tx.into_recovered().into_signed().envelope_encoded();
+        estimated_compression_ratio(
+            &l1_block_info,
+            &self.provider().chain_spec(),
+            block.timestamp,
+            &envelope,
+        )
+        .map(Some)
+    }
+
+    /// Helper function for `eth_sendRawTransaction` for Optimism.
+    ///
+    /// Forwards the raw transaction bytes to the configured sequencer endpoint, retrying transient
+    /// failures with the configured [SequencerRetryConfig](crate::eth::SequencerRetryConfig). A
+    /// JSON-RPC error returned by the sequencer is passed through to the caller.
+    /// This is a no-op if the sequencer endpoint is not configured.
+    #[cfg(feature = "optimism")]
+    pub async fn forward_to_sequencer(&self, tx: &Bytes) -> EthResult<()> {
+        if let Some(endpoint) = self.network().sequencer_endpoint() {
+            let body = serde_json::to_string(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "eth_sendRawTransaction",
+                "params": [format!("0x{}", alloy_primitives::hex::encode(tx))],
+                "id": self.network().chain_id()
+            }))
+            .map_err(|_| {
+                tracing::warn!(
+                    target = "rpc::eth",
+                    "Failed to serialize transaction for forwarding to sequencer"
+                );
+                EthApiError::InternalEthError
+            })?;
+
+            send_to_sequencer(
+                &self.inner.http_client,
+                endpoint,
+                body,
+                &self.sequencer_retry_config(),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Forwards the raw transactions to the configured sequencer endpoint in a single JSON-RPC
+    /// batch request and returns the result of every transaction in order.
+    ///
+    /// A transaction rejected by the sequencer only fails that transaction, while a failure of the
+    /// request itself fails the whole batch.
+    /// This is a no-op if the sequencer endpoint is not configured.
+    #[cfg(feature = "optimism")]
+    pub async fn forward_batch_to_sequencer(&self, txs: &[Bytes]) -> EthResult<Vec<EthResult<()>>> {
+        let Some(endpoint) = self.network().sequencer_endpoint().filter(|_| !txs.is_empty()) else {
+            return Ok(txs.iter().map(|_| Ok(())).collect())
+        };
+
+        let requests = txs
+            .iter()
+            .enumerate()
+            .map(|(id, tx)| {
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": "eth_sendRawTransaction",
+                    "params": [format!("0x{}", alloy_primitives::hex::encode(tx))],
+                    "id": id
+                })
+            })
+            .collect::<Vec<_>>();
+        let body = serde_json::to_string(&requests).map_err(|_| {
+            tracing::warn!(
+                target = "rpc::eth",
+                "Failed to serialize transactions for forwarding to sequencer"
+            );
+            EthApiError::InternalEthError
+        })?;
+
+        let results = send_batch_to_sequencer(
+            &self.inner.http_client,
+            endpoint,
+            body,
+            txs.len(),
+            &self.sequencer_retry_config(),
+        )
+        .await?;
+        Ok(results.into_iter().map(|res| res.map_err(Into::into)).collect())
+    }
+}
+
+impl<Provider, Pool, Network> EthApi<Provider, Pool, Network>
+where
+    Pool: TransactionPool + 'static,
+    Provider:
+        BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Network: NetworkInfo + Send + Sync + 'static,
+{
+    pub(crate) fn sign_request(
+        &self,
+        from: &Address,
+        request: TypedTransactionRequest,
+    ) -> EthResult<TransactionSigned> {
+        for signer in self.inner.signers.iter() {
+            if signer.is_signer_for(from) {
+                return match signer.sign_transaction(request, from) {
+                    Ok(tx) => Ok(tx),
+                    Err(e) => Err(e.into()),
+                }
+            }
+        }
+        Err(EthApiError::InvalidTransactionSignature)
+    }
+
+    /// Get Transaction by [BlockId] and the index of the transaction within that Block.
+    ///
+    /// Returns `Ok(None)` if the block does not exist, or the block as fewer transactions
+    pub(crate) async fn transaction_by_block_and_tx_index(
+        &self,
+        block_id: impl Into<BlockId>,
+        index: Index,
+    ) -> EthResult<Option<Transaction>> {
+        if let Some(block) = self.block_with_senders(block_id.into()).await? {
+            let block_hash = block.hash;
+            let block_number = block.number;
+            let base_fee_per_gas = block.base_fee_per_gas;
+            if let Some(tx) = block.into_transactions_ecrecovered().nth(index.into()) {
+                return Ok(Some(from_recovered_with_block_context(
+                    tx,
+                    block_hash,
+                    block_number,
+                    base_fee_per_gas,
+                    index.into(),
+                )))
+            }
+        }
+
+        Ok(None)
+    }
+}
+/// Represents from where a transaction was fetched.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TransactionSource {
+    /// Transaction exists in the pool (Pending)
+    Pool(TransactionSignedEcRecovered),
+    /// Transaction already included in a block
+    ///
+    /// This can be a historical block or a pending block (received from the CL)
+    Block {
+        /// Transaction fetched via provider
+        transaction: TransactionSignedEcRecovered,
+        /// Index of the transaction in the block
+        index: u64,
+        /// Hash of the block.
+        block_hash: B256,
+        /// Number of the block.
+        block_number: u64,
+        /// base fee of the block.
+        base_fee: Option<u64>,
+    },
+}
+
+/// The status of a transaction, see [EthApi::transaction_status].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionStatus {
+    /// The transaction was never seen or was dropped from the pool.
+    Unknown,
+    /// The transaction is in the pool, but not ready to be included in the next block.
+    Queued,
+    /// The transaction is in the pool and ready to be included in the next block.
+    Pending,
+    /// The transaction is included in a block.
+    Mined {
+        /// Hash of the block.
+        block_hash: B256,
+        /// Number of the block.
+        block_number: u64,
+        /// Index of the transaction in the block.
+        index: u64,
+    },
+}
+
+// === impl TransactionSource ===
+
+impl TransactionSource {
+    /// Consumes the type and returns the wrapped transaction.
+    pub fn into_recovered(self) -> TransactionSignedEcRecovered {
+        self.into()
+    }
+
+    /// Returns the transaction and block related info, if not pending
+    pub fn split(self) -> (TransactionSignedEcRecovered, TransactionInfo) {
+        match self {
+            TransactionSource::Pool(tx) => {
+                let hash = tx.hash();
+                (
+                    tx,
+                    TransactionInfo {
+                        hash: Some(hash),
+                        index: None,
+                        block_hash: None,
+                        block_number: None,
+                        base_fee: None,
+                    },
+                )
+            }
+            TransactionSource::Block { transaction, index, block_hash, block_number, base_fee } => {
+                let hash = transaction.hash();
+                (
+                    transaction,
+                    TransactionInfo {
+                        hash: Some(hash),
+                        index: Some(index),
+                        block_hash: Some(block_hash),
+                        block_number: Some(block_number),
+                        base_fee,
+                    },
+                )
+            }
+        }
+    }
+}
+
+impl From<TransactionSource> for TransactionSignedEcRecovered {
+    fn from(value: TransactionSource) -> Self {
+        match value {
+            TransactionSource::Pool(tx) => tx,
+            TransactionSource::Block { transaction, .. } => transaction,
+        }
+    }
+}
+
+impl From<TransactionSource> for Transaction {
+    fn from(value: TransactionSource) -> Self {
+        match value {
+            TransactionSource::Pool(tx) => reth_rpc_types_compat::transaction::from_recovered(tx),
+            TransactionSource::Block { transaction, index, block_hash, block_number, base_fee } => {
+                from_recovered_with_block_context(
+                    transaction,
+                    block_hash,
+                    block_number,
+                    base_fee,
+                    U256::from(index),
+                )
+            }
+        }
+    }
+}
+
+/// The receipts of the neighbouring transactions of a transaction in its block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdjacentReceipts {
+    /// Receipt of the previous transaction, `None` if the transaction is the first in the block.
+    pub previous: Option<TransactionReceipt>,
+    /// Receipt of the next transaction, `None` if the transaction is the last in the block.
+    pub next: Option<TransactionReceipt>,
+}
+
+/// The outcome of the inclusion checks of [EthApi::inclusion_eligibility].
+///
+/// Every flag is `true` if the check failed, so the default report is an eligible transaction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EligibilityReport {
+    /// The transaction's nonce is lower than the sender's nonce.
+    pub nonce_too_low: bool,
+    /// The transaction's nonce is higher than the sender's nonce, so it can't be included before
+    /// the missing nonces are.
+    pub nonce_too_high: bool,
+    /// The sender can't pay for the maximum cost of the transaction.
+    pub insufficient_balance: bool,
+    /// The transaction's gas limit is lower than its intrinsic gas.
+    pub intrinsic_gas_too_low: bool,
+    /// The transaction's max fee per gas is lower than the base fee of the latest block.
+    pub fee_cap_too_low: bool,
+}
+
+impl EligibilityReport {
+    /// Returns `true` if all checks passed.
+    pub fn is_eligible(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The fees of a call executed by [EthApi::transact_call_with_fees_at].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CallFees {
+    /// The gas price paid per unit of gas, derived from the block's base fee and the call's max
+    /// fee and priority fee.
+    pub effective_gas_price: U256,
+    /// Gas used by the call, after the refund was applied.
+    pub gas_used: u64,
+    /// Gas refunded at the end of the call.
+    pub gas_refunded: u64,
+    /// Intrinsic gas of the call under the rules of the block's fork, charged before execution.
+    pub intrinsic_gas: u64,
+}
+
+/// The instructions of a contract's code executed by a transaction, see
+/// [EthApi::spawn_opcode_coverage].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoverageReport {
+    /// Program counters of the executed instructions, in ascending order.
+    pub covered: Vec<usize>,
+    /// Program counters of the instructions that were not executed, in ascending order.
+    pub uncovered: Vec<usize>,
+}
+
+/// A `JUMP` or `JUMPI` executed by a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JumpPoint {
+    /// Address of the contract executing the jump.
+    pub contract: Address,
+    /// Program counter of the jump instruction.
+    pub pc: usize,
+    /// Program counter the jump targets.
+    ///
+    /// Note: a `JUMPI` only jumps there if its condition is non-zero.
+    pub destination: usize,
+    /// Gas remaining before the jump was executed.
+    pub gas_remaining: u64,
+}
+
+/// The outcome of a mined transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxOutcome {
+    /// Whether the transaction executed successfully.
+    pub success: bool,
+    /// The decoded revert reason, if the transaction failed and the revert data could be decoded.
+    pub revert_reason: Option<String>,
+}
+
+/// The fee paid by a mined transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxFee {
+    /// The total fee paid by the transaction in wei.
+    pub total_fee_wei: U256,
+    /// The effective price per gas the transaction paid.
+    pub effective_gas_price: U256,
+    /// The gas used by the transaction.
+    pub gas_used: u64,
+}
+
+/// Helper function to construct a transaction receipt
+///
+/// Note: This requires _all_ block receipts because we need to calculate the gas used by the
+/// transaction.
+pub(crate) fn build_transaction_receipt_with_block_receipts(
+    transaction: TransactionSigned,
+    meta: TransactionMeta,
+    receipt: Receipt,
+    all_receipts: &[Receipt],
+    #[cfg(feature = "optimism")] optimism_tx_meta: OptimismTxMeta,
+) -> EthResult<TransactionReceipt> {
+    // get the previous transaction cumulative gas used
+    let gas_used = if meta.index == 0 {
+        receipt.cumulative_gas_used
+    } else {
+        let prev_tx_idx = (meta.index - 1) as usize;
+        all_receipts
+            .get(prev_tx_idx)
+            .map(|prev_receipt| receipt.cumulative_gas_used - prev_receipt.cumulative_gas_used)
+            .unwrap_or_default()
+    };
+
+    // get number of logs in the block
+    let mut num_logs = 0;
+    for prev_receipt in all_receipts.iter().take(meta.index as usize) {
+        num_logs += prev_receipt.logs.len();
+    }
+
+    build_transaction_receipt_at(
+        transaction,
+        meta,
+        receipt,
+        gas_used,
+        num_logs,
+        #[cfg(feature = "optimism")]
+        optimism_tx_meta,
+    )
+}
+
+/// Helper function to construct a transaction receipt from the gas used by the transaction and the
+/// block-wide index of its first log.
+fn build_transaction_receipt_at(
+    transaction: TransactionSigned,
+    meta: TransactionMeta,
+    receipt: Receipt,
+    gas_used: u64,
+    first_log_index: usize,
+    #[cfg(feature = "optimism")] optimism_tx_meta: OptimismTxMeta,
+) -> EthResult<TransactionReceipt> {
+    // Note: we assume this transaction is valid, because it's mined (or part of pending block) and
+    // we don't need to check for pre EIP-2
+    let from =
+        transaction.recover_signer_unchecked().ok_or(EthApiError::InvalidTransactionSignature)?;
+
+    #[allow(clippy::needless_update)]
+    let mut res_receipt = TransactionReceipt {
+        transaction_hash: Some(meta.tx_hash),
+        transaction_index: U64::from(meta.index),
+        block_hash: Some(meta.block_hash),
+        block_number: Some(U256::from(meta.block_number)),
+        from,
+        to: None,
+        cumulative_gas_used: U256::from(receipt.cumulative_gas_used),
+        gas_used: Some(U256::from(gas_used)),
+        contract_address: None,
+        logs: Vec::with_capacity(receipt.logs.len()),
+        effective_gas_price: U128::from(transaction.effective_gas_price(meta.base_fee)),
+        transaction_type: transaction.transaction.tx_type().into(),
+        // TODO pre-byzantium receipts have a post-transaction state root
+        state_root: None,
+        logs_bloom: receipt.bloom_slow(),
+        status_code: if receipt.success { Some(U64::from(1)) } else { Some(U64::from(0)) },
+        // EIP-4844 fields
+        blob_gas_price: meta.excess_blob_gas.map(calc_blob_gasprice).map(U128::from),
+        blob_gas_used: transaction.transaction.blob_gas_used().map(U128::from),
+        // Optimism fields
+        #[cfg(feature = "optimism")]
+        deposit_nonce: receipt.deposit_nonce.map(U64::from),
+        ..Default::default()
+    };
+
+    #[cfg(feature = "optimism")]
+    if let Some(l1_block_info) = optimism_tx_meta.l1_block_info {
+        if !transaction.is_deposit() {
+            res_receipt.l1_fee = optimism_tx_meta.l1_fee;
+            res_receipt.l1_gas_used =
+                optimism_tx_meta.l1_data_gas.map(|dg| dg + l1_block_info.l1_fee_overhead);
+            res_receipt.l1_fee_scalar =
+                Some(l1_block_info.l1_fee_scalar.div(U256::from(1_000_000)));
+            res_receipt.l1_gas_price = Some(l1_block_info.l1_base_fee);
+        }
+    }
+
+    match transaction.transaction.kind() {
+        Create => {
+            res_receipt.contract_address = Some(from.create(transaction.transaction.nonce()));
+        }
+        Call(addr) => {
+            res_receipt.to = Some(*addr);
+        }
+    }
+
+    for (tx_log_idx, log) in receipt.logs.into_iter().enumerate() {
+        let rpclog = Log {
+            address: log.address,
+            topics: log.topics,
+            data: log.data,
+            block_hash: Some(meta.block_hash),
+            block_number: Some(U256::from(meta.block_number)),
+            transaction_hash: Some(meta.tx_hash),
+            transaction_index: Some(U256::from(meta.index)),
+            log_index: Some(U256::from(first_log_index + tx_log_idx)),
+            removed: false,
+        };
+        res_receipt.logs.push(rpclog);
+    }
+
+    Ok(res_receipt)
+}
+
+/// Returns [EthApiError::TracingTimeoutOrLimit] if the tracer aborted the execution because a
+/// configured limit was exceeded, in which case the recorded traces are incomplete.
+fn ensure_within_tracing_limits(inspector: &TracingInspector) -> EthResult<()> {
+    if inspector.is_limit_exceeded() {
+        return Err(EthApiError::TracingTimeoutOrLimit)
+    }
+    Ok(())
+}
+
+/// Estimates the ratio of the compressed size of the enveloped transaction to its actual size.
+///
+/// The L1 data fee model approximates the compressed size of the transaction by charging 16 gas
+/// per non-zero byte and 4 gas per zero byte, so the compressed size is the data gas divided by 16.
+#[cfg(feature = "optimism")]
+fn estimated_compression_ratio(
+    l1_block_info: &L1BlockInfo,
+    chain_spec: &ChainSpec,
+    timestamp: u64,
+    envelope: &Bytes,
+) -> EthResult<f64> {
+    if envelope.is_empty() {
+        return Ok(0.0)
+    }
+    let data_gas = l1_block_info
+        .l1_data_gas(chain_spec, timestamp, envelope)
+        .map_err(|_| EthApiError::InternalEthError)?;
+    Ok(data_gas.to::<u64>() as f64 / 16.0 / envelope.len() as f64)
+}
+
+/// The accounts and storage slots read and written by a transaction.
+///
+/// Accounts are identified by `(address, None)`, storage slots by `(address, Some(slot))`. All
+/// written state is also part of the read set. The block's beneficiary is excluded, since every
+/// transaction pays fees to it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccessSet {
+    /// The accounts and storage slots read by the transaction.
+    pub reads: HashSet<(Address, Option<U256>)>,
+    /// The accounts and storage slots modified by the transaction.
+    pub writes: HashSet<(Address, Option<U256>)>,
+}
+
+impl AccessSet {
+    /// Executes the transaction on top of the given state and collects the accessed state.
+    fn of_transaction(state: &StateProviderBox, env: Env) -> EthResult<Self> {
+        Self::of_transaction_with_db(CacheDB::new(StateProviderDatabase::new(state)), env)
+    }
+
+    /// Executes the transaction on top of the given database, without committing the changes,
+    /// and collects the accessed state.
+    fn of_transaction_with_db<DB>(mut db: CacheDB<DB>, env: Env) -> EthResult<Self>
+    where
+        DB: DatabaseRef,
+        EthApiError: From<DB::Error>,
+    {
+        let coinbase = env.block.coinbase;
+        let (ResultAndState { state: changes, .. }, _) = transact(&mut db, env)?;
+
+        let mut access = Self::default();
+        for (address, account) in changes {
+            if address == coinbase {
+                continue
+            }
+
+            access.reads.insert((address, None));
+            // the changes are not committed, so the database still holds the original account
+            let original = db.basic_ref(address)?.unwrap_or_default();
+            if original.nonce != account.info.nonce ||
+                original.balance != account.info.balance ||
+                original.code_hash != account.info.code_hash
+            {
+                access.writes.insert((address, None));
+            }
+
+            for (slot, value) in account.storage {
+                access.reads.insert((address, Some(slot)));
+                if value.is_changed() {
+                    access.writes.insert((address, Some(slot)));
+                }
+            }
+        }
+        Ok(access)
+    }
+
+    /// Returns `true` if either access set writes state the other one reads.
+    ///
+    /// Transactions with conflicting access sets can't be executed in parallel.
+    pub fn conflicts_with(&self, other: &Self) -> bool {
+        !self.writes.is_disjoint(&other.reads) || !other.writes.is_disjoint(&self.reads)
+    }
+}
+
+/// An [Inspector] that records the gas refund accrued by the top-level call, before the refund cap
+/// is applied.
+#[derive(Debug, Default)]
+struct RefundInspector {
+    /// Refund counter of the last call that ended, which is the top-level call once the
+    /// transaction is executed.
+    refund: i64,
+}
+
+impl<DB: Database> Inspector<DB> for RefundInspector {
+    fn call_end(
+        &mut self,
+        _data: &mut EVMData<'_, DB>,
+        _inputs: &CallInputs,
+        gas: Gas,
+        ret: InstructionResult,
+        out: Bytes,
+    ) -> (InstructionResult, Gas, Bytes) {
+        self.refund = gas.refunded();
+        (ret, gas, out)
+    }
+
+    fn create_end(
+        &mut self,
+        _data: &mut EVMData<'_, DB>,
+        _inputs: &CreateInputs,
+        ret: InstructionResult,
+        address: Option<Address>,
+        gas: Gas,
+        out: Bytes,
+    ) -> (InstructionResult, Option<Address>, Gas, Bytes) {
+        self.refund = gas.refunded();
+        (ret, address, gas, out)
+    }
+}
+
+/// An [Inspector] that records the largest memory size in bytes any call frame expanded to.
+#[derive(Debug, Default)]
+struct MemoryHighWaterMarkInspector {
+    /// Largest memory size of a call frame seen after any step.
+    high_water_mark: usize,
+}
+
+impl<DB: Database> Inspector<DB> for MemoryHighWaterMarkInspector {
+    fn step_end(&mut self, interp: &mut Interpreter<'_>, _data: &mut EVMData<'_, DB>) {
+        // checked after the step, so this includes the expansion of the step itself
+        self.high_water_mark =
+            self.high_water_mark.max(interp.shared_memory.context_memory().len());
+    }
+}
+
+/// Function selectors of well known DEX swap functions.
+const KNOWN_SWAP_SELECTORS: [[u8; 4]; 7] = [
+    // UniswapV2Router: swapExactTokensForTokens
+    [0x38, 0xed, 0x17, 0x39],
+    // UniswapV2Router: swapTokensForExactTokens
+    [0x88, 0x03, 0xdb, 0xee],
+    // UniswapV2Router: swapExactETHForTokens
+    [0x7f, 0xf3, 0x6a, 0xb5],
+    // UniswapV2Router: swapExactTokensForETH
+    [0x18, 0xcb, 0xaf, 0xe5],
+    // UniswapV3Router: exactInputSingle
+    [0x41, 0x4b, 0xf3, 0x89],
+    // UniswapV3Router: exactInput
+    [0xc0, 0x4b, 0x8d, 0x59],
+    // UniversalRouter: execute
+    [0x35, 0x93, 0x56, 0x4c],
+];
+
+/// The value moved through internal calls from which a transaction is considered worth
+/// front-running: 10 ether.
+const FRONTRUN_VALUE_THRESHOLD: U256 = U256::from_limbs([10_000_000_000_000_000_000, 0, 0, 0]);
+
+/// The estimated size of a storage slot in the state in bytes: the 32 byte key and the 32 byte
+/// value.
+pub const STORAGE_SLOT_STATE_BYTES: i64 = 64;
+
+/// The estimated size of an account in the state in bytes, without its code and storage: the 8
+/// byte nonce, the 32 byte balance, the 32 byte storage root and the 32 byte code hash.
+pub const ACCOUNT_STATE_BYTES: i64 = 104;
+
+/// Returns the current unix timestamp in seconds.
+#[inline]
+fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+/// Returns `true` if the opcode reads block context that is not fixed by the state.
+#[inline]
+fn is_block_context_opcode(op: u8) -> bool {
+    matches!(
+        op,
+        opcode::BLOCKHASH |
+            opcode::COINBASE |
+            opcode::TIMESTAMP |
+            opcode::NUMBER |
+            opcode::DIFFICULTY |
+            opcode::GASLIMIT |
+            opcode::BASEFEE
+    )
+}
+
+/// Returns the Shannon entropy of the given bytes in bits per byte.
+fn shannon_entropy(data: &[u8]) -> f64 {
+    let mut counts = [0usize; 256];
+    for byte in data {
+        counts[*byte as usize] += 1;
+    }
+    let len = data.len() as f64;
+    counts
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let p = *count as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Returns the 4-byte function selector of the given calldata, if any.
+#[inline]
+pub(crate) fn function_selector(input: &[u8]) -> Option<[u8; 4]> {
+    input.get(..4).map(|selector| selector.try_into().expect("slice has length 4"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eth::{
+            api::test_utils::{build_test_eth_api, build_test_eth_api_with_limits},
+            cache::EthStateCache,
+            error::{RpcInvalidTransactionError, RpcPoolError},
+            gas_oracle::GasPriceOracle,
+            FeeHistoryCache, FeeHistoryCacheConfig, TracingLimits,
+        },
+        BlockingTaskPool, EthApi,
+    };
+    use reth_interfaces::test_utils::generators::{
+        self, generate_keys, random_block, sign_tx_with_key_pair,
+    };
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::{
+        constants::ETHEREUM_BLOCK_GAS_LIMIT, hex_literal::hex, AccessList, AccessListItem, Block,
+        Bytes, TxEip1559, TxEip2930, TxLegacy,
+    };
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider, NoopProvider};
+    use reth_transaction_pool::{test_utils::testing_pool, TransactionPool};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn send_raw_transaction() {
+        let noop_provider = NoopProvider::default();
+        let noop_network_provider = NoopNetwork::default();
+
+        let pool = testing_pool();
+
+        let cache = EthStateCache::spawn(noop_provider, Default::default());
+        let fee_history_cache =
+            FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
+        let eth_api = EthApi::new(
+            noop_provider,
+            pool.clone(),
+            noop_network_provider,
+            cache.clone(),
+            GasPriceOracle::new(noop_provider, Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            fee_history_cache,
+        );
+
+        // https://etherscan.io/tx/0xa694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d
+        let tx_1 = Bytes::from(hex!("02f871018303579880850555633d1b82520894eee27662c2b8eba3cd936a23f039f3189633e4c887ad591c62bdaeb180c080a07ea72c68abfb8fca1bd964f0f99132ed9280261bdca3e549546c0205e800f7d0a05b4ef3039e9c9b9babc179a1878fb825b5aaf5aed2fa8744854150157b08d6f3"));
+
+        let tx_1_result = eth_api.send_raw_transaction(tx_1).await.unwrap();
+        assert_eq!(
+            pool.len(),
+            1,
+            "expect 1 transactions in the pool, but pool size is {}",
+            pool.len()
+        );
+
+        // https://etherscan.io/tx/0x48816c2f32c29d152b0d86ff706f39869e6c1f01dc2fe59a3c1f9ecf39384694
+        let tx_2 = Bytes::from(hex!("02f9043c018202b7843b9aca00850c807d37a08304d21d94ef1c6e67703c7bd7107eed8303fbe6ec2554bf6b881bc16d674ec80000b903c43593564c000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000063e2d99f00000000000000000000000000000000000000000000000000000000000000030b000800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000c000000000000000000000000000000000000000000000000000000000000001e0000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000001bc16d674ec80000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000065717fe021ea67801d1088cc80099004b05b64600000000000000000000000000000000000000000000000001bc16d674ec80000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002bc02aaa39b223fe8d0a0e5c4f27ead9083c756cc20001f4a0b86991c6218b36c1d19d4a2e9eb0ce3606eb480000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000000180000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009e95fd5965fd1f1a6f0d4600000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48000000000000000000000000428dca9537116148616a5a3e44035af17238fe9dc080a0c6ec1e41f5c0b9511c49b171ad4e04c6bb419c74d99fe9891d74126ec6e4e879a032069a753d7a2cfa158df95421724d24c0e9501593c09905abf3699b4a4405ce"));
+
+        let tx_2_result = eth_api.send_raw_transaction(tx_2).await.unwrap();
+        assert_eq!(
+            pool.len(),
+            2,
+            "expect 2 transactions in the pool, but pool size is {}",
+            pool.len()
+        );
+
+        assert!(pool.get(&tx_1_result).is_some(), "tx1 not found in the pool");
+        assert!(pool.get(&tx_2_result).is_some(), "tx2 not found in the pool");
+    }
+
+    #[tokio::test]
+    async fn transaction_known_selector() {
+        let eth_api = build_test_eth_api(NoopProvider::default());
+
+        // https://etherscan.io/tx/0x48816c2f32c29d152b0d86ff706f39869e6c1f01dc2fe59a3c1f9ecf39384694
+        let tx = Bytes::from(hex!("02f9043c018202b7843b9aca00850c807d37a08304d21d94ef1c6e67703c7bd7107eed8303fbe6ec2554bf6b881bc16d674ec80000b903c43593564c000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000063e2d99f00000000000000000000000000000000000000000000000000000000000000030b000800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000c000000000000000000000000000000000000000000000000000000000000001e0000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000001bc16d674ec80000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000065717fe021ea67801d1088cc80099004b05b64600000000000000000000000000000000000000000000000001bc16d674ec80000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002bc02aaa39b223fe8d0a0e5c4f27ead9083c756cc20001f4a0b86991c6218b36c1d19d4a2e9eb0ce3606eb480000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000000180000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009e95fd5965fd1f1a6f0d4600000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48000000000000000000000000428dca9537116148616a5a3e44035af17238fe9dc080a0c6ec1e41f5c0b9511c49b171ad4e04c6bb419c74d99fe9891d74126ec6e4e879a032069a753d7a2cfa158df95421724d24c0e9501593c09905abf3699b4a4405ce"));
+        let hash = eth_api.send_raw_transaction(tx).await.unwrap();
+
+        let selectors = HashMap::from([([0x35, 0x93, 0x56, 0x4c], "execute".to_string())]);
+        let label = eth_api.transaction_known_selector(hash, selectors).await.unwrap();
+        assert_eq!(label, Some(Some("execute".to_string())));
+
+        let label = eth_api.transaction_known_selector(hash, HashMap::new()).await.unwrap();
+        assert_eq!(label, Some(None));
+
+        let label =
+            eth_api.transaction_known_selector(B256::random(), HashMap::new()).await.unwrap();
+        assert_eq!(label, None);
+    }
+
+    #[tokio::test]
+    async fn adjacent_receipts() {
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 1, None, Some(3), Some(0));
+        let receipts = block
+            .body
+            .iter()
+            .enumerate()
+            .map(|(idx, tx)| Receipt {
+                tx_type: tx.tx_type(),
+                success: true,
+                cumulative_gas_used: 21_000 * (idx as u64 + 1),
+                ..Default::default()
+            })
+            .collect();
+
+        let provider = MockEthProvider::default();
+        provider.add_block(block.hash, block.clone().unseal());
+        provider.add_receipts(block.hash, receipts);
+
+        let eth_api = build_test_eth_api(provider);
+
+        let adjacent = eth_api.adjacent_receipts(block.body[1].hash).await.unwrap().unwrap();
+        assert_eq!(adjacent.previous.unwrap().transaction_hash, Some(block.body[0].hash));
+        assert_eq!(adjacent.next.unwrap().transaction_hash, Some(block.body[2].hash));
+
+        let adjacent = eth_api.adjacent_receipts(block.body[2].hash).await.unwrap().unwrap();
+        assert_eq!(adjacent.previous.unwrap().transaction_hash, Some(block.body[1].hash));
+        assert!(adjacent.next.is_none());
+
+        assert!(eth_api.adjacent_receipts(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn call_uses_randomness() {
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Default::default());
+
+        // PREVRANDAO PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        let prevrandao = Address::random();
+        provider.add_account(
+            prevrandao,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("4460005260206000f3"))),
+        );
+
+        // PUSH1 1 PUSH1 2 ADD PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        let arithmetic = Address::random();
+        provider.add_account(
+            arithmetic,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("600160020160005260206000f3"))),
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        let request = CallRequest { to: Some(prevrandao), ..Default::default() };
+        assert!(eth_api
+            .call_uses_randomness_at(request, BlockNumberOrTag::Latest.into())
+            .await
+            .unwrap());
+
+        let request = CallRequest { to: Some(arithmetic), ..Default::default() };
+        assert!(!eth_api
+            .call_uses_randomness_at(request, BlockNumberOrTag::Latest.into())
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn inclusion_latency() {
+        let mut rng = generators::rng();
+        let mut block = random_block(&mut rng, 1, None, Some(1), Some(0)).unseal();
+        block.header.timestamp = 1_000;
+        let tx_hash = block.body[0].hash;
+
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), block);
+
+        let eth_api = build_test_eth_api(provider);
+        assert!(eth_api.inclusion_latency(tx_hash).await.unwrap().is_none());
+
+        eth_api.record_transaction_arrival(tx_hash, 988).await;
+        assert_eq!(eth_api.inclusion_latency(tx_hash).await.unwrap(), Some(12));
+    }
+
+    #[cfg(feature = "optimism")]
+    #[test]
+    fn estimated_compression_ratio_of_data_heavy_tx() {
+        let l1_block_info = L1BlockInfo {
+            l1_base_fee: U256::ZERO,
+            l1_fee_overhead: U256::ZERO,
+            l1_fee_scalar: U256::ZERO,
+        };
+        let mut envelope = vec![0u8; 1000];
+        envelope[0] = 0x02;
+
+        // 1 non-zero byte at 16 gas and 999 zero bytes at 4 gas each
+        let ratio = estimated_compression_ratio(
+            &l1_block_info,
+            &reth_primitives::BASE_MAINNET,
+            u64::MAX,
+            &envelope.into(),
+        )
+        .unwrap();
+        assert_eq!(ratio, (16.0 + 999.0 * 4.0) / 16.0 / 1000.0);
+        assert!(ratio > 0.25 && ratio < 1.0);
+    }
+
+    #[tokio::test]
+    async fn transactions_conflict() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 4);
+
+        // PUSH1 1 PUSH1 0 SSTORE STOP
+        let counter = Address::random();
+        let call = |key_pair, to| {
+            let tx = reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(to),
+                ..Default::default()
+            });
+            sign_tx_with_key_pair(key_pair, tx)
+        };
+        let body = vec![
+            call(keys[0], counter),
+            call(keys[1], counter),
+            call(keys[2], Address::random()),
+            call(keys[3], Address::random()),
+        ];
+        let hashes = body.iter().map(|tx| tx.hash).collect::<Vec<_>>();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            counter,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("6001600055"))),
+        );
+        provider.add_block(B256::random(), Block { body, ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let conflict = eth_api.spawn_transactions_conflict(hashes[0], hashes[1]).await.unwrap();
+        assert_eq!(conflict, Some(true));
+
+        let conflict = eth_api.spawn_transactions_conflict(hashes[2], hashes[3]).await.unwrap();
+        assert_eq!(conflict, Some(false));
+
+        let conflict =
+            eth_api.spawn_transactions_conflict(hashes[0], B256::random()).await.unwrap();
+        assert_eq!(conflict, None);
+    }
+
+    #[tokio::test]
+    async fn call_count() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        let callee = Address::random();
+        // CALL(GAS, callee, 0, 0, 0, 0, 0) STOP
+        let mut code =
+            hex!("600060006000600060007300000000000000000000000000000000000000005af100").to_vec();
+        code[11..31].copy_from_slice(callee.as_slice());
+        let caller = Address::random();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            caller,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from(code)),
+        );
+        provider.add_account(
+            callee,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from_static(&hex!("00"))),
+        );
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(caller),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        assert_eq!(eth_api.spawn_call_count(hash).await.unwrap(), Some(2));
+        assert_eq!(eth_api.spawn_call_count(B256::random()).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn gas_price_percentile_in_block() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+        let body = [
+            reth_primitives::Transaction::Legacy(TxLegacy { gas_price: 15, ..Default::default() }),
+            reth_primitives::Transaction::Eip1559(TxEip1559 {
+                max_fee_per_gas: 30,
+                max_priority_fee_per_gas: 12,
+                ..Default::default()
+            }),
+            reth_primitives::Transaction::Legacy(TxLegacy { gas_price: 12, ..Default::default() }),
+        ]
+        .into_iter()
+        .map(|tx| sign_tx_with_key_pair(key_pair, tx))
+        .collect::<Vec<_>>();
+        let hashes = body.iter().map(|tx| tx.hash).collect::<Vec<_>>();
+
+        let provider = MockEthProvider::default();
+        provider.add_block(
+            B256::random(),
+            Block {
+                header: Header { base_fee_per_gas: Some(10), ..Default::default() },
+                body,
+                ..Default::default()
+            },
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        let percentile = eth_api.gas_price_percentile_in_block(hashes[1]).await.unwrap();
+        assert_eq!(percentile, Some(100.0));
+
+        let percentile = eth_api.gas_price_percentile_in_block(hashes[2]).await.unwrap().unwrap();
+        assert!((percentile - 100.0 / 3.0).abs() < f64::EPSILON * 100.0);
+
+        assert!(eth_api.gas_price_percentile_in_block(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn jump_gas_profile() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // counts down from 3 to 0 in a loop:
+        // PUSH1 3 JUMPDEST PUSH1 1 SWAP1 SUB DUP1 PUSH1 2 JUMPI STOP
+        let looping = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            looping,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("60035b600190038060025700"))),
+        );
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(looping),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let jumps = eth_api.spawn_jump_gas_profile(hash).await.unwrap().unwrap();
+        assert_eq!(jumps.len(), 3);
+        assert!(jumps
+            .iter()
+            .all(|jump| jump.contract == looping && jump.pc == 10 && jump.destination == 2));
+        assert!(jumps.windows(2).all(|pair| pair[0].gas_remaining > pair[1].gas_remaining));
+
+        assert!(eth_api.spawn_jump_gas_profile(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn transaction_outcome() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // reverts with `Error("nope")`, copying the abi encoded revert data from the code:
+        // PUSH1 100 PUSH1 12 PUSH1 0 CODECOPY PUSH1 100 PUSH1 0 REVERT
+        let reverting = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            reverting,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from_static(&hex!(
+                "6064600c60003960646000fd08c379a00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000046e6f706500000000000000000000000000000000000000000000000000000000"
+            ))),
+        );
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(reverting),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+        let block_hash = B256::random();
+        provider.add_block(block_hash, Block { body: vec![tx], ..Default::default() });
+        provider.add_receipts(block_hash, vec![Receipt { success: false, ..Default::default() }]);
+
+        let eth_api = build_test_eth_api(provider);
+
+        let outcome = eth_api.transaction_outcome(hash).await.unwrap().unwrap();
+        assert!(!outcome.success);
+        assert!(outcome.revert_reason.unwrap().ends_with("nope"));
+
+        assert!(eth_api.transaction_outcome(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn tip_vs_suggestion() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Block::default());
+
+        let eth_api = build_test_eth_api(provider);
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Eip1559(TxEip1559 {
+                chain_id: 1,
+                gas_limit: 21_000,
+                max_fee_per_gas: 3_000_000_000,
+                max_priority_fee_per_gas: 1_500_000_000,
+                to: Call(Address::random()),
+                ..Default::default()
+            }),
+        );
+        let hash = eth_api.send_raw_transaction(tx.envelope_encoded()).await.unwrap();
+
+        let suggested = eth_api.gas_oracle().suggest_tip_cap().await.unwrap();
+        let (tip, suggestion) = eth_api.tip_vs_suggestion(hash).await.unwrap().unwrap();
+        assert_eq!(tip, U256::from(1_500_000_000u64));
+        assert_eq!(suggestion, suggested);
+
+        assert!(eth_api.tip_vs_suggestion(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn pool_pending_gas() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+        let eth_api = build_test_eth_api(NoopProvider::default());
+
+        for (key_pair, nonce, gas_limit) in
+            [(keys[0], 0, 21_000), (keys[1], 0, 50_000), (keys[1], 5, 80_000)]
+        {
+            let tx = sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    nonce,
+                    gas_limit,
+                    to: Call(Address::random()),
+                    ..Default::default()
+                }),
+            );
+            eth_api.send_raw_transaction(tx.envelope_encoded()).await.unwrap();
+        }
+
+        // the transaction with a nonce gap is queued, not pending
+        assert_eq!(eth_api.pool_pending_gas().unwrap(), 71_000);
+    }
+
+    #[tokio::test]
+    async fn external_code_reads() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // PUSH20 <target> EXTCODESIZE STOP
+        let target = Address::random();
+        let mut code = vec![opcode::PUSH20];
+        code.extend_from_slice(target.as_slice());
+        code.extend_from_slice(&[opcode::EXTCODESIZE, opcode::STOP]);
+
+        let inspecting = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            inspecting,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from(code)),
+        );
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(inspecting),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let reads = eth_api.spawn_external_code_reads(hash).await.unwrap().unwrap();
+        assert_eq!(reads, vec![target]);
+
+        assert!(eth_api.spawn_external_code_reads(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn transaction_fee() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_price: 7_000_000_000,
+                gas_limit: 21_000,
+                to: Call(Address::random()),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+
+        let provider = MockEthProvider::default();
+        let block_hash = B256::random();
+        provider.add_block(block_hash, Block { body: vec![tx], ..Default::default() });
+        provider.add_receipts(
+            block_hash,
+            vec![Receipt { success: true, cumulative_gas_used: 21_000, ..Default::default() }],
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        let fee = eth_api.transaction_fee(hash).await.unwrap().unwrap();
+        assert_eq!(fee.gas_used, 21_000);
+        assert_eq!(fee.effective_gas_price, U256::from(7_000_000_000u64));
+        assert_eq!(fee.total_fee_wei, fee.effective_gas_price * U256::from(fee.gas_used));
+
+        assert!(eth_api.transaction_fee(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn next_block_transactions() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 3);
+
+        let provider = MockEthProvider::default();
+        provider.add_block(
+            B256::random(),
+            Block {
+                header: Header { gas_limit: 50_000, ..Default::default() },
+                ..Default::default()
+            },
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        for key_pair in keys {
+            let tx = sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_limit: 21_000,
+                    to: Call(Address::random()),
+                    ..Default::default()
+                }),
+            );
+            eth_api.send_raw_transaction(tx.envelope_encoded()).await.unwrap();
+        }
+
+        // only two of the three transactions fit into the block
+        let selected = eth_api.next_block_transactions().unwrap();
+        assert_eq!(selected.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn log_gas() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // emits 64 bytes of data with a single topic: PUSH1 42 PUSH1 64 PUSH1 0 LOG1 STOP
+        let logging = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            logging,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("602a60406000a100"))),
+        );
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(logging),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        // base + topic + data + expanding memory to two words
+        let log_gas = eth_api.spawn_log_gas(hash).await.unwrap().unwrap();
+        assert_eq!(log_gas, 375 + 375 + 8 * 64 + 6);
+
+        assert!(eth_api.spawn_log_gas(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn transaction_input_entropy() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+        let eth_api = build_test_eth_api(NoopProvider::default());
+
+        let zeroes = Bytes::from(vec![0u8; 1024]);
+        let random = Bytes::from((0..32).flat_map(|_| B256::random().0).collect::<Vec<_>>());
+
+        let mut hashes = Vec::new();
+        for (key_pair, input) in keys.into_iter().zip([zeroes, random]) {
+            let tx = sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_limit: 100_000,
+                    to: Call(Address::random()),
+                    input,
+                    ..Default::default()
+                }),
+            );
+            hashes.push(eth_api.send_raw_transaction(tx.envelope_encoded()).await.unwrap());
+        }
+
+        let low = eth_api.transaction_input_entropy(hashes[0]).await.unwrap().unwrap();
+        assert_eq!(low, 0.0);
+        let high = eth_api.transaction_input_entropy(hashes[1]).await.unwrap().unwrap();
+        assert!(high > 7.0);
+
+        assert!(eth_api.transaction_input_entropy(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn pending_transaction_target_block() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        let provider = MockEthProvider::default();
+        provider.add_block(
+            B256::random(),
+            Block { header: Header { number: 5, ..Default::default() }, ..Default::default() },
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 21_000,
+                to: Call(Address::random()),
+                ..Default::default()
+            }),
+        );
+        let hash = eth_api.send_raw_transaction(tx.envelope_encoded()).await.unwrap();
+
+        let pending = eth_api.pending_block_env_and_cfg().unwrap();
+        let target = eth_api.pending_transaction_target_block(hash).await.unwrap();
+        assert_eq!(target, Some(pending.origin.header().number));
+        assert_eq!(target, Some(6));
+
+        assert!(eth_api.pending_transaction_target_block(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn persisted_storage_diff() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // PUSH1 42 PUSH1 1 SSTORE STOP
+        let storing = Address::random();
+        let slot = B256::with_last_byte(1);
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            storing,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("602a60015500")))
+                .extend_storage([(slot, U256::from(7))]),
+        );
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(storing),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let diff = eth_api.spawn_persisted_storage_diff(hash).await.unwrap().unwrap();
+        assert_eq!(
+            diff,
+            HashMap::from([(
+                storing,
+                HashMap::from([(slot, (B256::with_last_byte(7), B256::with_last_byte(42)))])
+            )])
+        );
+
+        assert!(eth_api.spawn_persisted_storage_diff(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn frontrun_opportunity() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+
+        // forwards the call value: CALL(GAS, <pool>, CALLVALUE, 0, 0, 0, 0)
+        let pool = Address::random();
+        let mut code = hex!("6000600060006000").to_vec();
+        code.push(opcode::CALLVALUE);
+        code.push(opcode::PUSH20);
+        code.extend_from_slice(pool.as_slice());
+        code.extend_from_slice(&[opcode::GAS, opcode::CALL, opcode::STOP]);
+
+        let router = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Block::default());
+        provider
+            .add_account(router, ExtendedAccount::new(0, U256::ZERO).with_bytecode(code.into()));
+
+        let txs = [
+            // swapExactETHForTokens through the router
+            (Call(router), Bytes::from_static(&hex!("7ff36ab5"))),
+            // plain transfer
+            (Call(Address::random()), Bytes::default()),
+        ];
+        let eth_api = build_test_eth_api(provider.clone());
+
+        let mut hashes = Vec::new();
+        for (key_pair, (to, input)) in keys.into_iter().zip(txs) {
+            let tx = sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_limit: 100_000,
+                    to,
+                    value: U256::from(1_000_000_000_000_000_000u128).into(),
+                    input,
+                    ..Default::default()
+                }),
+            );
+            provider.add_account(
+                tx.recover_signer().unwrap(),
+                ExtendedAccount::new(0, U256::from(2_000_000_000_000_000_000u128)),
+            );
+            hashes.push(eth_api.send_raw_transaction(tx.envelope_encoded()).await.unwrap());
+        }
+
+        assert_eq!(eth_api.spawn_frontrun_opportunity(hashes[0]).await.unwrap(), Some(true));
+        assert_eq!(eth_api.spawn_frontrun_opportunity(hashes[1]).await.unwrap(), Some(false));
+        assert!(eth_api.spawn_frontrun_opportunity(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn calldata_gas_cost() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+
+        let provider = MockEthProvider::default();
+        let mut hashes = Vec::new();
+        // mainnet activates Istanbul at block 9069000
+        for (key_pair, number) in keys.into_iter().zip([10_000_000, 0]) {
+            let tx = sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_limit: 100_000,
+                    to: Call(Address::random()),
+                    input: Bytes::from_static(&[0, 0, 1, 2]),
+                    ..Default::default()
+                }),
+            );
+            hashes.push(tx.hash);
+            provider.add_block(
+                B256::random(),
+                Block {
+                    header: Header { number, ..Default::default() },
+                    body: vec![tx],
+                    ..Default::default()
+                },
+            );
+        }
+
+        let eth_api = build_test_eth_api(provider);
+
+        assert_eq!(eth_api.calldata_gas_cost(hashes[0]).await.unwrap(), Some(2 * 4 + 2 * 16));
+        assert_eq!(eth_api.calldata_gas_cost(hashes[1]).await.unwrap(), Some(2 * 4 + 2 * 68));
+        assert!(eth_api.calldata_gas_cost(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn call_with_gas_limit() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // PUSH1 42 PUSH1 1 SSTORE STOP
+        let storing = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            storing,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("602a60015500"))),
+        );
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(storing),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let res = eth_api.call_with_gas_limit_at(hash, 100_000).await.unwrap();
+        assert!(res.result.is_success());
+
+        // not enough gas left for the SSTORE after paying the intrinsic gas
+        let res = eth_api.call_with_gas_limit_at(hash, 21_100).await.unwrap();
+        assert!(matches!(
+            res.result,
+            ExecutionResult::Halt {
+                reason: reth_primitives::revm_primitives::Halt::OutOfGas(_),
+                ..
+            }
+        ));
+
+        assert!(eth_api.call_with_gas_limit_at(B256::random(), 100_000).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn blockhash_reads() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // PUSH1 9 BLOCKHASH STOP
+        let reading = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            reading,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("60094000"))),
+        );
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(reading),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+        provider.add_block(
+            B256::random(),
+            Block {
+                header: Header { number: 10, ..Default::default() },
+                body: vec![tx],
+                ..Default::default()
+            },
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        assert_eq!(eth_api.spawn_blockhash_reads(hash).await.unwrap(), Some(vec![9]));
+        assert!(eth_api.spawn_blockhash_reads(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn pool_gas_price_histogram() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 3);
+        let eth_api = build_test_eth_api(NoopProvider::default());
+
+        for (key_pair, gas_price) in keys.into_iter().zip([1, 15, 20]) {
+            // zero gas limit so the unfunded senders can afford the transactions
+            let tx = sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_price,
+                    to: Call(Address::random()),
+                    ..Default::default()
+                }),
+            );
+            eth_api.send_raw_transaction(tx.envelope_encoded()).await.unwrap();
+        }
+
+        let buckets = vec![U256::ZERO, U256::from(10), U256::from(100)];
+        assert_eq!(eth_api.pool_gas_price_histogram(buckets).unwrap(), vec![1, 2, 0]);
+    }
+
+    #[tokio::test]
+    async fn balance_change_for() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        let recipient = Address::random();
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 21_000,
+                to: Call(recipient),
+                value: U256::from(5).into(),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+
+        let provider = MockEthProvider::default();
+        provider
+            .add_account(tx.recover_signer().unwrap(), ExtendedAccount::new(0, U256::from(100)));
+        provider.add_account(recipient, ExtendedAccount::new(0, U256::from(10)));
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let change = eth_api.spawn_balance_change_for(hash, recipient).await.unwrap();
+        assert_eq!(change, Some((U256::from(10), U256::from(15))));
+
+        // untouched accounts keep their balance
+        let change = eth_api.spawn_balance_change_for(hash, Address::random()).await.unwrap();
+        assert_eq!(change, Some((U256::ZERO, U256::ZERO)));
+
+        assert!(eth_api
+            .spawn_balance_change_for(B256::random(), recipient)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn sender_tx_position_in_block() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+
+        let body = [(keys[0], 0), (keys[1], 0), (keys[0], 1)]
+            .into_iter()
+            .map(|(key_pair, nonce)| {
+                sign_tx_with_key_pair(
+                    key_pair,
+                    reth_primitives::Transaction::Legacy(TxLegacy {
+                        chain_id: Some(1),
+                        nonce,
+                        gas_limit: 21_000,
+                        to: Call(Address::random()),
+                        ..Default::default()
+                    }),
+                )
+            })
+            .collect::<Vec<_>>();
+        let hashes = body.iter().map(|tx| tx.hash).collect::<Vec<_>>();
+
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Block { body, ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        assert_eq!(eth_api.sender_tx_position_in_block(hashes[0]).await.unwrap(), Some((0, 2)));
+        assert_eq!(eth_api.sender_tx_position_in_block(hashes[1]).await.unwrap(), Some((0, 1)));
+        assert_eq!(eth_api.sender_tx_position_in_block(hashes[2]).await.unwrap(), Some((1, 2)));
+        assert!(eth_api.sender_tx_position_in_block(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn minimum_balance_required() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_price: 10,
+                gas_limit: 21_000,
+                to: Call(Address::random()),
+                value: U256::from(5).into(),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            tx.recover_signer().unwrap(),
+            ExtendedAccount::new(0, U256::from(1_000_000)),
+        );
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        // a simple transfer uses its entire gas limit
+        let minimum = eth_api.spawn_minimum_balance_required(hash).await.unwrap();
+        assert_eq!(minimum, Some(U256::from(5 + 21_000 * 10)));
+
+        assert!(eth_api.spawn_minimum_balance_required(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn minimum_balance_required_eip1559() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Eip1559(TxEip1559 {
+                chain_id: 1,
+                gas_limit: 50_000,
+                max_fee_per_gas: 30,
+                max_priority_fee_per_gas: 5,
+                to: Call(Address::random()),
+                value: U256::from(5).into(),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            tx.recover_signer().unwrap(),
+            ExtendedAccount::new(0, U256::from(10_000_000)),
+        );
+        provider.add_block(
+            B256::random(),
+            Block {
+                header: Header { base_fee_per_gas: Some(20), ..Default::default() },
+                body: vec![tx],
+                ..Default::default()
+            },
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        // only the gas used is paid, at the base fee plus the priority fee
+        let minimum = eth_api.spawn_minimum_balance_required(hash).await.unwrap();
+        assert_eq!(minimum, Some(U256::from(5 + 21_000 * 25)));
+        assert!(minimum < Some(U256::from(5 + 50_000 * 30)));
+    }
+
+    #[tokio::test]
+    async fn interacts_with_same_block_deploy() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+
+        // deploys a contract with empty code
+        let deploy = sign_tx_with_key_pair(
+            keys[0],
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Create,
+                input: Bytes::from_static(&hex!("00")),
+                ..Default::default()
+            }),
+        );
+        let created = deploy.recover_signer().unwrap().create(0);
+        let call = sign_tx_with_key_pair(
+            keys[1],
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(created),
+                ..Default::default()
+            }),
+        );
+        let (deploy_hash, call_hash) = (deploy.hash, call.hash);
+
+        let provider = MockEthProvider::default();
+        provider
+            .add_block(B256::random(), Block { body: vec![deploy, call], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let interacts = eth_api.spawn_interacts_with_same_block_deploy(call_hash).await.unwrap();
+        assert_eq!(interacts, Some(true));
+        let interacts = eth_api.spawn_interacts_with_same_block_deploy(deploy_hash).await.unwrap();
+        assert_eq!(interacts, Some(false));
+        assert!(eth_api
+            .spawn_interacts_with_same_block_deploy(B256::random())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn memory_high_water_mark() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // stores a word at offset 4096: PUSH1 1 PUSH2 0x1000 MSTORE STOP
+        let expanding = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            expanding,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("60016110005200"))),
+        );
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(expanding),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let high_water_mark = eth_api.spawn_memory_high_water_mark(hash).await.unwrap();
+        assert_eq!(high_water_mark, Some(4096 + 32));
+
+        assert!(eth_api.spawn_memory_high_water_mark(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn transaction_access_set() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+
+        // PUSH1 42 PUSH1 1 SSTORE STOP
+        let storing = Address::random();
+        let recipient = Address::random();
+        let transfer = sign_tx_with_key_pair(
+            keys[0],
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 21_000,
+                to: Call(recipient),
+                value: U256::from(5).into(),
+                ..Default::default()
+            }),
+        );
+        let store = sign_tx_with_key_pair(
+            keys[1],
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(storing),
+                ..Default::default()
+            }),
+        );
+        let (transfer_sender, store_sender) =
+            (transfer.recover_signer().unwrap(), store.recover_signer().unwrap());
+        let (transfer_hash, store_hash) = (transfer.hash, store.hash);
+
+        let provider = MockEthProvider::default();
+        provider.add_account(transfer_sender, ExtendedAccount::new(0, U256::from(100)));
+        provider.add_account(
+            storing,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("602a60015500"))),
+        );
+        provider
+            .add_block(B256::random(), Block { body: vec![transfer, store], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let access = eth_api.spawn_transaction_access_set(transfer_hash).await.unwrap().unwrap();
+        let accounts = HashSet::from([(transfer_sender, None), (recipient, None)]);
+        assert_eq!(access.reads, accounts);
+        assert_eq!(access.writes, accounts);
+
+        let access = eth_api.spawn_transaction_access_set(store_hash).await.unwrap().unwrap();
+        let slot = (storing, Some(U256::from(1)));
+        assert_eq!(access.reads, HashSet::from([(store_sender, None), (storing, None), slot]));
+        // only the nonce of the sender and the slot change
+        assert_eq!(access.writes, HashSet::from([(store_sender, None), slot]));
+
+        assert!(eth_api.spawn_transaction_access_set(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn verify_transaction_nonce() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+
+        let body = [(keys[0], 3), (keys[1], 7), (keys[0], 4)]
+            .into_iter()
+            .map(|(key_pair, nonce)| {
+                sign_tx_with_key_pair(
+                    key_pair,
+                    reth_primitives::Transaction::Legacy(TxLegacy {
+                        chain_id: Some(1),
+                        nonce,
+                        gas_limit: 21_000,
+                        to: Call(Address::random()),
+                        ..Default::default()
+                    }),
+                )
+            })
+            .collect::<Vec<_>>();
+        let hashes = body.iter().map(|tx| tx.hash).collect::<Vec<_>>();
+
+        let provider = MockEthProvider::default();
+        provider
+            .add_account(body[0].recover_signer().unwrap(), ExtendedAccount::new(3, U256::ZERO));
+        provider.add_block(B256::random(), Block { body, ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        assert_eq!(eth_api.verify_transaction_nonce(hashes[0]).await.unwrap(), Some(true));
+        assert_eq!(eth_api.verify_transaction_nonce(hashes[2]).await.unwrap(), Some(true));
+        // the second sender's account is new, so it should have used nonce 0
+        assert_eq!(eth_api.verify_transaction_nonce(hashes[1]).await.unwrap(), Some(false));
+        assert!(eth_api.verify_transaction_nonce(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn revert_bubble_path() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+
+        // PUSH1 0 PUSH1 0 REVERT
+        let reverting = Address::random();
+        // CALL(gas, reverting, 0, 0, 0, 0, 0) PUSH1 0 PUSH1 0 REVERT
+        let caller = Address::random();
+        let caller_code =
+            [&hex!("6000600060006000600073")[..], &reverting[..], &hex!("5af160006000fd")[..]]
+                .concat();
+
+        let sign = |key_pair, to| {
+            sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_limit: 100_000,
+                    to: Call(to),
+                    ..Default::default()
+                }),
+            )
+        };
+        let (reverted, transfer) = (sign(keys[0], caller), sign(keys[1], Address::random()));
+        let (reverted_hash, transfer_hash) = (reverted.hash, transfer.hash);
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            reverting,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("60006000fd"))),
+        );
+        provider.add_account(
+            caller,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(caller_code.into()),
+        );
+        provider.add_block(
+            B256::random(),
+            Block { body: vec![reverted, transfer], ..Default::default() },
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        let path = eth_api.spawn_revert_bubble_path(reverted_hash).await.unwrap();
+        assert_eq!(path, Some(vec![reverting, caller]));
+
+        let path = eth_api.spawn_revert_bubble_path(transfer_hash).await.unwrap();
+        assert_eq!(path, Some(Vec::new()));
+
+        assert!(eth_api.spawn_revert_bubble_path(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn call_with_modified_value() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // CALLVALUE PUSH1 0 SSTORE STOP
+        let payable = Address::random();
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(payable),
+                value: U256::from(5).into(),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+
+        let provider = MockEthProvider::default();
+        provider
+            .add_account(tx.recover_signer().unwrap(), ExtendedAccount::new(0, U256::from(100)));
+        provider.add_account(
+            payable,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("3460005500"))),
+        );
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let original = eth_api.call_with_modified_value_at(hash, U256::from(5)).await.unwrap();
+        let modified = eth_api.call_with_modified_value_at(hash, U256::from(7)).await.unwrap();
+        assert!(original.result.is_success());
+        assert!(modified.result.is_success());
+
+        let stored = |res: &ResultAndState| {
+            let account = &res.state[&payable];
+            (account.info.balance, account.storage[&U256::ZERO].present_value)
+        };
+        assert_eq!(stored(&original), (U256::from(5), U256::from(5)));
+        assert_eq!(stored(&modified), (U256::from(7), U256::from(7)));
+
+        assert!(eth_api.call_with_modified_value_at(B256::random(), U256::from(7)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn creation_init_code_size() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Block::default());
+
+        let eth_api = build_test_eth_api(provider);
+
+        let sign = |key_pair, to| {
+            sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_limit: 100_000,
+                    to,
+                    input: Bytes::from_static(&hex!("600a600c600039600a6000f3")),
+                    ..Default::default()
+                }),
+            )
+        };
+        let deploy = sign(keys[0], Create);
+        let call = sign(keys[1], Call(Address::random()));
+        let deploy = eth_api.send_raw_transaction(deploy.envelope_encoded()).await.unwrap();
+        let call = eth_api.send_raw_transaction(call.envelope_encoded()).await.unwrap();
+
+        assert_eq!(eth_api.creation_init_code_size(deploy).await.unwrap(), Some(12));
+        assert_eq!(eth_api.creation_init_code_size(call).await.unwrap(), None);
+        assert_eq!(eth_api.creation_init_code_size(B256::random()).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn block_receipts() {
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 1, None, Some(3), Some(0));
+        let receipts = block
+            .body
+            .iter()
+            .zip([(21_000, 1), (50_000, 0), (90_000, 2)])
+            .map(|(tx, (cumulative_gas_used, logs))| reth_primitives::Receipt {
+                tx_type: tx.tx_type(),
+                success: true,
+                cumulative_gas_used,
+                logs: vec![Default::default(); logs],
+                ..Default::default()
+            })
+            .collect();
+
+        let provider = MockEthProvider::default();
+        provider.add_block(block.hash, block.clone().unseal());
+        provider.add_receipts(block.hash, receipts);
+
+        let eth_api = build_test_eth_api(provider);
+
+        let receipts = eth_api.block_receipts(block.hash.into()).await.unwrap().unwrap();
+        let gas_used = receipts.iter().map(|r| r.gas_used.unwrap().to::<u64>()).collect::<Vec<_>>();
+        assert_eq!(gas_used, vec![21_000, 29_000, 40_000]);
+        let log_indices = receipts
+            .iter()
+            .flat_map(|r| r.logs.iter().map(|log| log.log_index.unwrap().to::<u64>()))
+            .collect::<Vec<_>>();
+        assert_eq!(log_indices, vec![0, 1, 2]);
+        assert_eq!(receipts[2].logs[0].transaction_hash, Some(block.body[2].hash));
+
+        assert!(eth_api.block_receipts(B256::random().into()).await.unwrap().is_none());
+        assert!(eth_api.block_receipts(BlockNumberOrTag::Pending.into()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn touched_slot() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+
+        // PUSH1 42 PUSH1 1 SSTORE STOP
+        let storing = Address::random();
+        // PUSH1 42 PUSH1 2 SSTORE STOP
+        let other = Address::random();
+
+        let sign = |key_pair, to| {
+            sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_limit: 100_000,
+                    to: Call(to),
+                    ..Default::default()
+                }),
+            )
+        };
+        let (writes, misses) = (sign(keys[0], storing), sign(keys[1], other));
+        let (writes_hash, misses_hash) = (writes.hash, misses.hash);
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            storing,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("602a60015500"))),
+        );
+        provider.add_account(
+            other,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("602a60025500"))),
+        );
+        provider
+            .add_block(B256::random(), Block { body: vec![writes, misses], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let slot = B256::with_last_byte(1);
+        assert_eq!(
+            eth_api.spawn_touched_slot(writes_hash, storing, slot).await.unwrap(),
+            Some(true)
+        );
+        assert_eq!(
+            eth_api.spawn_touched_slot(misses_hash, other, slot).await.unwrap(),
+            Some(false)
+        );
+        assert!(eth_api.spawn_touched_slot(B256::random(), storing, slot).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn interaction_count() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // STOP
+        let callee = Address::random();
+        // CALL(gas, callee, 0, 0, 0, 0, 0) STOP
+        let caller = Address::random();
+        let caller_code =
+            [&hex!("6000600060006000600073")[..], &callee[..], &hex!("5af100")[..]].concat();
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(caller),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            callee,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from_static(&hex!("00"))),
+        );
+        provider.add_account(
+            caller,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(caller_code.into()),
+        );
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        // the sender, the caller and the callee
+        assert_eq!(eth_api.spawn_interaction_count(hash).await.unwrap(), Some(3));
+        assert!(eth_api.spawn_interaction_count(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn trace_block_until_par_matches_sequential() {
+        let mut rng = generators::rng();
+        let recipient = Address::random();
+        let provider = MockEthProvider::default();
+
+        // all transfers go to the same recipient, so every transaction depends on the previous
+        let body = generate_keys(&mut rng, 50)
+            .into_iter()
+            .map(|key_pair| {
+                let tx = sign_tx_with_key_pair(
+                    key_pair,
+                    reth_primitives::Transaction::Legacy(TxLegacy {
+                        chain_id: Some(1),
+                        gas_limit: 21_000,
+                        to: Call(recipient),
+                        value: U256::from(5).into(),
+                        ..Default::default()
+                    }),
+                );
+                provider.add_account(
+                    tx.recover_signer().unwrap(),
+                    ExtendedAccount::new(0, U256::from(100)),
+                );
+                tx
+            })
+            .collect::<Vec<_>>();
+        let block_hash = B256::random();
+        provider.add_block(block_hash, Block { body, ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let trace =
+            move |tx_info: TransactionInfo,
+                  inspector: TracingInspector,
+                  result: ExecutionResult,
+                  state: &State,
+                  _: &CacheDB<StateProviderDatabase<StateProviderBox>>| {
+                Ok((
+                    tx_info.hash,
+                    tx_info.index,
+                    result.gas_used(),
+                    inspector.get_traces().nodes().len(),
+                    state.get(&recipient).map(|account| account.info.balance),
+                ))
+            };
+        let config = TracingInspectorConfig::default_parity();
+
+        for highest_index in [None, Some(10)] {
+            let sequential = eth_api
+                .trace_block_until(block_hash.into(), highest_index, config, trace)
+                .await
+                .unwrap()
+                .unwrap();
+            let parallel = eth_api
+                .trace_block_until_par(block_hash.into(), highest_index, config, trace)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(parallel.len(), highest_index.unwrap_or(50) as usize);
+            assert_eq!(parallel, sequential);
+            assert_eq!(parallel.last().unwrap().4, Some(U256::from(5 * parallel.len())));
+        }
+
+        assert!(eth_api
+            .trace_block_until_par(B256::random().into(), None, config, trace)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn gas_price_base_fee_ratio() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+
+        let tx = sign_tx_with_key_pair(
+            keys[0],
+            reth_primitives::Transaction::Eip1559(TxEip1559 {
+                chain_id: 1,
+                gas_limit: 21_000,
+                max_fee_per_gas: 30,
+                max_priority_fee_per_gas: 5,
+                to: Call(Address::random()),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+        let pre_london = sign_tx_with_key_pair(
+            keys[1],
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_price: 10,
+                gas_limit: 21_000,
+                to: Call(Address::random()),
+                ..Default::default()
+            }),
+        );
+        let pre_london_hash = pre_london.hash;
+
+        let provider = MockEthProvider::default();
+        provider.add_block(
+            B256::random(),
+            Block {
+                header: Header { base_fee_per_gas: Some(20), ..Default::default() },
+                body: vec![tx],
+                ..Default::default()
+            },
+        );
+        provider.add_block(B256::random(), Block { body: vec![pre_london], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        // the sender paid the base fee plus a tip of 5
+        let ratio = eth_api.gas_price_base_fee_ratio(hash).await.unwrap().unwrap();
+        assert_eq!(ratio, 1.25);
+        assert!(ratio > 1.0);
+
+        assert!(eth_api.gas_price_base_fee_ratio(pre_london_hash).await.unwrap().is_none());
+        assert!(eth_api.gas_price_base_fee_ratio(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn raw_transaction_by_hash() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+
+        let sign = |key_pair| {
+            sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_limit: 21_000,
+                    to: Call(Address::random()),
+                    ..Default::default()
+                }),
+            )
+        };
+        let (mined, pending) = (sign(keys[0]), sign(keys[1]));
+        let (mined_raw, pending_raw) = (mined.envelope_encoded(), pending.envelope_encoded());
+        let mined_hash = mined.hash;
+
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Block { body: vec![mined], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+        let pending_hash = eth_api.send_raw_transaction(pending_raw.clone()).await.unwrap();
+
+        assert_eq!(eth_api.raw_transaction_by_hash(mined_hash).await.unwrap(), Some(mined_raw));
+        assert_eq!(eth_api.raw_transaction_by_hash(pending_hash).await.unwrap(), Some(pending_raw));
+        assert!(eth_api.raw_transaction_by_hash(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn call_batch() {
+        // PUSH1 1 SLOAD PUSH1 12 JUMPI PUSH1 1 PUSH1 1 SSTORE STOP JUMPDEST PUSH1 0 PUSH1 0 REVERT
+        //
+        // sets slot 1 on the first call and reverts on every further call
+        let once = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Block::default());
+        provider.add_account(
+            once,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("600154600c576001600155005b60006000fd"))),
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        let request = CallRequest { to: Some(once), ..Default::default() };
+        let results = eth_api
+            .spawn_with_call_batch_at(
+                vec![request.clone(); 3],
+                BlockNumberOrTag::Latest.into(),
+                EvmOverrides::default(),
+                |_, results| Ok(results),
+            )
+            .await
+            .unwrap();
+        // the second call sees the slot set by the first one, the third call is never executed
+        assert_eq!(results.len(), 2);
+        assert!(results[0].result.is_success());
+        assert!(matches!(results[1].result, ExecutionResult::Revert { .. }));
+
+        // not enough gas for the SSTORE
+        let constrained = CallRequest { gas: Some(U256::from(21_100)), ..request.clone() };
+        let results = eth_api
+            .spawn_with_call_batch_at(
+                vec![constrained, request],
+                BlockNumberOrTag::Latest.into(),
+                EvmOverrides::default(),
+                |_, results| Ok(results),
+            )
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(matches!(results[0].result, ExecutionResult::Halt { .. }));
+    }
+
+    #[tokio::test]
+    async fn trace_at_with_overrides() {
+        // BASEFEE PUSH1 0 SSTORE STOP
+        let contract = Address::random();
+        let caller = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Block::default());
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("4860005500"))),
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        let env = Env {
+            tx: revm::primitives::TxEnv {
+                caller,
+                gas_limit: 100_000,
+                gas_price: U256::from(7),
+                transact_to: revm::primitives::TransactTo::Call(contract),
+                value: U256::from(5),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let balance = U256::from(1_000_000_000);
+        let overrides = EvmOverrides::new(
+            Some(HashMap::from([(
+                caller,
+                reth_rpc_types::state::AccountOverride {
+                    balance: Some(balance),
+                    ..Default::default()
+                },
+            )])),
+            Some(Box::new(reth_rpc_types::BlockOverrides {
+                base_fee: Some(U256::from(7)),
+                ..Default::default()
+            })),
+        );
+
+        let (gas_used, caller_balance, stored) = eth_api
+            .trace_at(
+                env.clone(),
+                TracingInspectorConfig::default_parity(),
+                BlockNumberOrTag::Latest.into(),
+                overrides,
+                |inspector, res| {
+                    assert!(inspector.get_traces().nodes()[0].trace.success);
+                    Ok((
+                        res.result.gas_used(),
+                        res.state[&caller].info.balance,
+                        res.state[&contract].storage[&U256::ZERO].present_value,
+                    ))
+                },
+            )
+            .unwrap();
+        // the sender paid for the transfer and the gas with the overridden balance
+        assert_eq!(caller_balance, balance - U256::from(5) - U256::from(gas_used * 7));
+        assert_eq!(stored, U256::from(7));
+
+        // without the overrides the sender can't pay for the transaction
+        assert!(eth_api
+            .trace_at(
+                env,
+                TracingInspectorConfig::default_parity(),
+                BlockNumberOrTag::Latest.into(),
+                Default::default(),
+                |_, _| Ok(()),
+            )
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn transaction_status() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 3);
+
+        let sign = |key_pair, nonce| {
+            sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    nonce,
+                    gas_limit: 21_000,
+                    to: Call(Address::random()),
+                    ..Default::default()
+                }),
+            )
+        };
+        let mined = sign(keys[0], 0);
+        let mined_hash = mined.hash;
+
+        let provider = MockEthProvider::default();
+        let block_hash = B256::random();
+        provider.add_block(
+            block_hash,
+            Block {
+                header: Header { number: 7, ..Default::default() },
+                body: vec![sign(keys[1], 0), mined],
+                ..Default::default()
+            },
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        let pending =
+            eth_api.send_raw_transaction(sign(keys[2], 0).envelope_encoded()).await.unwrap();
+        // nonce gap, the sender's nonce is 0
+        let queued =
+            eth_api.send_raw_transaction(sign(keys[2], 5).envelope_encoded()).await.unwrap();
+
+        assert_eq!(
+            eth_api.transaction_status(mined_hash).await.unwrap(),
+            TransactionStatus::Mined { block_hash, block_number: 7, index: 1 }
+        );
+        assert_eq!(eth_api.transaction_status(pending).await.unwrap(), TransactionStatus::Pending);
+        assert_eq!(eth_api.transaction_status(queued).await.unwrap(), TransactionStatus::Queued);
+        assert_eq!(
+            eth_api.transaction_status(B256::random()).await.unwrap(),
+            TransactionStatus::Unknown
+        );
+    }
+
+    #[tokio::test]
+    async fn max_call_depth() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // calls itself with the calldata word decremented by one until it reaches zero:
+        // PUSH1 0 CALLDATALOAD DUP1 ISZERO PUSH1 0x1e JUMPI PUSH1 1 SWAP1 SUB PUSH1 0 MSTORE
+        // CALL(gas, address, 0, 0, 32, 0, 0) POP STOP JUMPDEST STOP
+        let recursive = Address::random();
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 500_000,
+                to: Call(recursive),
+                input: U256::from(5).to_be_bytes::<32>().to_vec().into(),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            recursive,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from_static(&hex!(
+                "6000358015601e576001900360005260006000602060006000305af150005b00"
+            ))),
+        );
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        assert_eq!(eth_api.spawn_max_call_depth(hash).await.unwrap(), Some(5));
+        assert!(eth_api.spawn_max_call_depth(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn call_still_valid_at_latest() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+
+        let sign = |key_pair| {
+            sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_limit: 21_000,
+                    to: Call(Address::random()),
+                    value: U256::from(1_000).into(),
+                    ..Default::default()
+                }),
+            )
+        };
+        let (drained, funded) = (sign(keys[0]), sign(keys[1]));
+
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Block::default());
+        // the balance of the first sender was drained by an intervening transaction
+        provider
+            .add_account(drained.recover_signer().unwrap(), ExtendedAccount::new(1, U256::ZERO));
+        provider.add_account(
+            funded.recover_signer().unwrap(),
+            ExtendedAccount::new(0, U256::from(1_000)),
+        );
+
+        let eth_api = build_test_eth_api(provider);
+        let drained = eth_api.send_raw_transaction(drained.envelope_encoded()).await.unwrap();
+        let funded = eth_api.send_raw_transaction(funded.envelope_encoded()).await.unwrap();
+
+        assert_eq!(eth_api.call_still_valid_at_latest(drained).await.unwrap(), Some(false));
+        assert_eq!(eth_api.call_still_valid_at_latest(funded).await.unwrap(), Some(true));
+        assert!(eth_api.call_still_valid_at_latest(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn fill_missing_fees() {
+        let provider = MockEthProvider::default();
+        provider.add_block(
+            B256::random(),
+            Block {
+                header: Header { base_fee_per_gas: Some(1_000_000_000), ..Default::default() },
+                ..Default::default()
+            },
+        );
+        let eth_api = build_test_eth_api(provider);
+
+        let mut request = TransactionRequest { to: Some(Address::random()), ..Default::default() };
+        eth_api.fill_missing_fees(&mut request).await.unwrap();
+        let priority_fee = request.max_priority_fee_per_gas.unwrap();
+        let max_fee = request.max_fee_per_gas.unwrap();
+        assert!(priority_fee > U128::ZERO);
+        assert!(max_fee >= priority_fee);
+        assert!(request.gas_price.is_none());
+        assert!(matches!(request.into_typed_request(), Some(TypedTransactionRequest::EIP1559(_))));
+
+        let mut request = TransactionRequest {
+            to: Some(Address::random()),
+            access_list: Some(Default::default()),
+            ..Default::default()
+        };
+        eth_api.fill_missing_fees(&mut request).await.unwrap();
+        assert!(request.gas_price.unwrap() > U128::ZERO);
+        assert!(matches!(request.into_typed_request(), Some(TypedTransactionRequest::EIP2930(_))));
+
+        // explicit fees are kept
+        let mut request = TransactionRequest {
+            max_fee_per_gas: Some(U128::from(7)),
+            max_priority_fee_per_gas: Some(U128::from(3)),
+            ..Default::default()
+        };
+        eth_api.fill_missing_fees(&mut request).await.unwrap();
+        assert_eq!(request.max_fee_per_gas, Some(U128::from(7)));
+        assert_eq!(request.max_priority_fee_per_gas, Some(U128::from(3)));
+    }
+
+    #[tokio::test]
+    async fn fill_missing_fees_without_base_fee() {
+        // the latest block has no base fee, so London is not active
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Block::default());
+        let eth_api = build_test_eth_api(provider);
+
+        let mut request = TransactionRequest { to: Some(Address::random()), ..Default::default() };
+        eth_api.fill_missing_fees(&mut request).await.unwrap();
+        assert!(request.gas_price.unwrap() > U128::ZERO);
+        assert!(request.max_fee_per_gas.is_none());
+        assert!(request.max_priority_fee_per_gas.is_none());
+        assert!(matches!(request.into_typed_request(), Some(TypedTransactionRequest::Legacy(_))));
+    }
+
+    #[tokio::test]
+    async fn access_list_gas() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+
+        let with_list = sign_tx_with_key_pair(
+            keys[0],
+            reth_primitives::Transaction::Eip2930(TxEip2930 {
+                chain_id: 1,
+                gas_limit: 100_000,
+                to: Call(Address::random()),
+                access_list: AccessList(vec![
+                    AccessListItem {
+                        address: Address::random(),
+                        storage_keys: vec![B256::ZERO, B256::with_last_byte(1)],
+                    },
+                    AccessListItem { address: Address::random(), storage_keys: Vec::new() },
+                ]),
+                ..Default::default()
+            }),
+        );
+        let without_list = sign_tx_with_key_pair(
+            keys[1],
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 21_000,
+                to: Call(Address::random()),
+                ..Default::default()
+            }),
+        );
+        let (with_list_hash, without_list_hash) = (with_list.hash, without_list.hash);
+
+        let provider = MockEthProvider::default();
+        provider.add_block(
+            B256::random(),
+            Block { body: vec![with_list, without_list], ..Default::default() },
+        );
+        let eth_api = build_test_eth_api(provider);
+
+        // two addresses and two storage keys
+        assert_eq!(
+            eth_api.access_list_gas(with_list_hash).await.unwrap(),
+            Some(2 * 2400 + 2 * 1900)
+        );
+        assert_eq!(eth_api.access_list_gas(without_list_hash).await.unwrap(), Some(0));
+        assert!(eth_api.access_list_gas(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn tracing_step_limit() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // counts down from 1000: PUSH2 1000 JUMPDEST PUSH1 1 SWAP1 SUB DUP1 PUSH1 3 JUMPI STOP
+        let looping = Address::random();
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(looping),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            looping,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("6103e85b600190038060035700"))),
+        );
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api_with_limits(
+            provider.clone(),
+            TracingLimits { max_steps: Some(100), ..Default::default() },
+        );
+
+        let config = TracingInspectorConfig::default_geth();
+        let err = eth_api
+            .spawn_trace_transaction_in_block(hash, config, |_, _, _, _| Ok(()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EthApiError::TracingTimeoutOrLimit));
+
+        // traces without steps aren't limited
+        let config = TracingInspectorConfig::default_parity();
+        assert!(eth_api
+            .spawn_trace_transaction_in_block(hash, config, |_, _, _, _| Ok(()))
+            .await
+            .unwrap()
+            .is_some());
+
+        // the default limits allow the full trace
+        let eth_api = build_test_eth_api(provider);
+        let steps = eth_api
+            .spawn_trace_transaction_in_block(
+                hash,
+                TracingInspectorConfig::default_geth(),
+                |_, inspector, _, _| Ok(inspector.get_traces().nodes()[0].trace.steps.len()),
+            )
+            .await
+            .unwrap();
+        assert!(steps.unwrap() > 100);
+    }
+
+    #[tokio::test]
+    async fn tracing_memory_limit() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // expands the memory to 4128 bytes: PUSH1 1 PUSH2 0x1000 MSTORE STOP
+        let contract = Address::random();
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(contract),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("60016110005200"))),
+        );
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let config = TracingInspectorConfig::default_geth();
+
+        // the single snapshot of the last step exceeds the limit and is never recorded
+        let eth_api = build_test_eth_api_with_limits(
+            provider.clone(),
+            TracingLimits { max_memory_bytes: Some(4096), ..Default::default() },
+        );
+        let err = eth_api
+            .spawn_trace_transaction_in_block(hash, config, |_, _, _, _| Ok(()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EthApiError::TracingTimeoutOrLimit));
+
+        // reaching the limit exactly is allowed
+        let eth_api = build_test_eth_api_with_limits(
+            provider,
+            TracingLimits { max_memory_bytes: Some(4128), ..Default::default() },
+        );
+        assert!(eth_api
+            .spawn_trace_transaction_in_block(hash, config, |_, _, _, _| Ok(()))
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn tracing_timeout() {
+        let timeout = Duration::from_millis(50);
+        let eth_api = build_test_eth_api_with_limits(
+            MockEthProvider::default(),
+            TracingLimits { timeout: Some(timeout), ..Default::default() },
+        );
+
+        let err = eth_api
+            .spawn_tracing_task_with(|_| {
+                std::thread::sleep(Duration::from_secs(1));
+                Ok(())
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EthApiError::TracingTimeout(t) if t == timeout));
+
+        assert_eq!(eth_api.spawn_tracing_task_with(|_| Ok(1)).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn execution_gas_used() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(Address::random()),
+                input: Bytes::from_static(&[1, 0]),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+
+        let provider = MockEthProvider::default();
+        let block_hash = B256::random();
+        provider.add_block(block_hash, Block { body: vec![tx], ..Default::default() });
+        provider.add_receipts(
+            block_hash,
+            vec![Receipt { success: true, cumulative_gas_used: 45_000, ..Default::default() }],
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        // intrinsic gas: 21000 base + 16 for the non-zero byte + 4 for the zero byte
+        let intrinsic_gas = 21_000 + 16 + 4;
+        assert_eq!(eth_api.execution_gas_used(hash).await.unwrap(), Some(45_000 - intrinsic_gas));
+        assert!(eth_api.execution_gas_used(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn trace_transaction_prestate() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // stores the calldata word in slot 0: PUSH1 0 CALLDATALOAD PUSH1 0 SSTORE STOP
+        let contract = Address::random();
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(contract),
+                input: U256::from(7).to_be_bytes::<32>().to_vec().into(),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("60003560005500")))
+                .extend_storage([(B256::ZERO, U256::from(1))]),
+        );
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let Some(PreStateFrame::Default(prestate)) =
+            eth_api.trace_transaction_prestate(hash, false).await.unwrap()
+        else {
+            panic!("expected default prestate frame")
+        };
+        assert_eq!(prestate.0[&contract].storage[&B256::ZERO], B256::with_last_byte(1));
+
+        let Some(PreStateFrame::Diff(diff)) =
+            eth_api.trace_transaction_prestate(hash, true).await.unwrap()
+        else {
+            panic!("expected diff prestate frame")
+        };
+        assert_eq!(diff.pre[&contract].storage[&B256::ZERO], B256::with_last_byte(1));
+        assert_eq!(diff.post[&contract].storage[&B256::ZERO], B256::with_last_byte(7));
+
+        assert!(eth_api.trace_transaction_prestate(B256::random(), false).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn trace_transaction_call_frame() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // reverts with `Error("nope")`: CODECOPY(0, 12, 100) REVERT(0, 100), followed by the
+        // abi-encoded error
+        let mut reason = [0u8; 32];
+        reason[..4].copy_from_slice(b"nope");
+        let child = Address::random();
+        let child_code = [
+            &hex!("6064600c60003960646000fd08c379a0")[..],
+            &U256::from(32).to_be_bytes::<32>()[..],
+            &U256::from(4).to_be_bytes::<32>()[..],
+            &reason[..],
+        ]
+        .concat();
+
+        // LOG0(0, 0) CALL(gas, child, 0, 0, 0, 0, 0) STOP
+        let parent = Address::random();
+        let parent_code =
+            [&hex!("6000600060a06000600060006000600073")[..], &child[..], &hex!("5af100")[..]]
+                .concat();
+
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(parent),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            parent,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(parent_code.into()),
+        );
+        provider.add_account(
+            child,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(child_code.into()),
+        );
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let frame = eth_api.trace_transaction_call_frame(hash, true, false).await.unwrap().unwrap();
+        assert_eq!(frame.to, Some(parent));
+        assert!(frame.error.is_none());
+        assert_eq!(frame.logs.len(), 1);
+        assert_eq!(frame.calls.len(), 1);
+        let child_frame = &frame.calls[0];
+        assert_eq!(child_frame.to, Some(child));
+        assert!(child_frame.error.is_some());
+        assert_eq!(child_frame.revert_reason.as_deref(), Some("nope"));
+
+        let frame = eth_api.trace_transaction_call_frame(hash, false, true).await.unwrap().unwrap();
+        assert!(frame.logs.is_empty());
+        assert!(frame.calls.is_empty());
+
+        assert!(eth_api
+            .trace_transaction_call_frame(B256::random(), false, false)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn compare_access_list_gas() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // PUSH1 0 SLOAD STOP
+        let contract = Address::random();
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Eip2930(TxEip2930 {
+                chain_id: 1,
+                gas_limit: 100_000,
+                to: Call(contract),
+                access_list: AccessList(vec![AccessListItem {
+                    address: contract,
+                    storage_keys: vec![B256::ZERO],
+                }]),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("60005400"))),
+        );
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let (with_list, without_list) =
+            eth_api.compare_access_list_gas(hash).await.unwrap().unwrap();
+        // the list costs 2400 for the address and 1900 for the key, but the warm SLOAD saves 2000
+        assert_eq!(with_list, without_list + 2_400 + 1_900 - 2_000);
+
+        assert!(eth_api.compare_access_list_gas(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn send_raw_transactions() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+        let raw = |key_pair| {
+            sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_limit: 21_000,
+                    to: Call(Address::random()),
+                    ..Default::default()
+                }),
+            )
+            .envelope_encoded()
+        };
+
+        let eth_api = build_test_eth_api(NoopProvider::default());
+        let pool = eth_api.pool();
+
+        let results = eth_api
+            .send_raw_transactions(
+                vec![raw(keys[0]), Bytes::from_static(&[0xde, 0xad]), raw(keys[1])],
+                TransactionOrigin::External,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 3);
+        let first = results[0].as_ref().unwrap();
+        assert!(results[1].is_err());
+        let last = results[2].as_ref().unwrap();
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.get(first).unwrap().origin, TransactionOrigin::External);
+        assert!(pool.get(last).is_some());
+    }
+
+    #[tokio::test]
+    async fn spawn_max_possible_refund() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // clears slots 0 and 1: SSTORE(0, 0) SSTORE(1, 0) STOP
+        let contract = Address::random();
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(contract),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("6000600055600060015500")))
+                .extend_storage([
+                    (B256::ZERO, U256::from(1)),
+                    (B256::with_last_byte(1), U256::from(1)),
+                ]),
+        );
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        // every cleared slot refunds 4800
+        let refund = eth_api.spawn_max_possible_refund(hash).await.unwrap().unwrap();
+        assert_eq!(refund, 2 * 4_800);
+
+        // 21000 intrinsic gas and two cold SSTOREs resetting a slot, the cap is a fifth of it
+        let capped = (21_000 + 2 * (3 + 3 + 2_100 + 2_900)) / 5;
+        assert!(refund > capped);
+
+        assert!(eth_api.spawn_max_possible_refund(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn send_raw_transaction_pool_errors() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+        let raw = |gas_price| {
+            sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_price,
+                    gas_limit: 21_000,
+                    to: Call(Address::random()),
+                    ..Default::default()
+                }),
+            )
+            .envelope_encoded()
+        };
+
+        let eth_api = build_test_eth_api(NoopProvider::default());
+
+        let tx = raw(100);
+        let hash = eth_api.send_raw_transaction(tx.clone()).await.unwrap();
+
+        // resubmitting the same transaction returns its hash
+        let err = eth_api.send_raw_transaction(tx).await.unwrap_err();
+        assert!(
+            matches!(err, EthApiError::PoolError(RpcPoolError::AlreadyKnown(known)) if known == hash)
+        );
+
+        // the replacement doesn't pay the required price bump
+        let err = eth_api.send_raw_transaction(raw(105)).await.unwrap_err();
+        assert!(matches!(err, EthApiError::PoolError(RpcPoolError::ReplaceUnderpriced)));
+        assert_eq!(err.to_string(), "replacement transaction underpriced");
+    }
+
+    #[tokio::test]
+    async fn inclusion_eligibility() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+        let sign = |tx: TxEip1559| {
+            sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Eip1559(TxEip1559 {
+                    chain_id: 1,
+                    to: Call(Address::random()),
+                    ..tx
+                }),
+            )
+        };
+        let valid =
+            TxEip1559 { nonce: 1, gas_limit: 21_000, max_fee_per_gas: 20, ..Default::default() };
+        let sender = sign(valid.clone()).recover_signer().unwrap();
+
+        let provider = MockEthProvider::default();
+        provider.add_block(
+            B256::random(),
+            Block {
+                header: Header { base_fee_per_gas: Some(10), ..Default::default() },
+                ..Default::default()
+            },
+        );
+        provider.add_account(sender, ExtendedAccount::new(1, U256::from(1_000_000)));
+
+        let eth_api = build_test_eth_api(provider);
+        let check = |tx| {
+            let raw = sign(tx).envelope_encoded();
+            let eth_api = eth_api.clone();
+            async move { eth_api.inclusion_eligibility(raw).await.unwrap() }
+        };
+
+        let report = check(valid.clone()).await;
+        assert!(report.is_eligible());
+
+        let report = check(TxEip1559 { nonce: 0, ..valid.clone() }).await;
+        assert_eq!(report, EligibilityReport { nonce_too_low: true, ..Default::default() });
+
+        let report = check(TxEip1559 { nonce: 2, ..valid.clone() }).await;
+        assert_eq!(report, EligibilityReport { nonce_too_high: true, ..Default::default() });
+
+        let report =
+            check(TxEip1559 { value: U256::from(1_000_000).into(), ..valid.clone() }).await;
+        assert_eq!(report, EligibilityReport { insufficient_balance: true, ..Default::default() });
+
+        let report = check(TxEip1559 { gas_limit: 20_000, ..valid.clone() }).await;
+        assert_eq!(report, EligibilityReport { intrinsic_gas_too_low: true, ..Default::default() });
+
+        let report = check(TxEip1559 { max_fee_per_gas: 5, ..valid }).await;
+        assert_eq!(report, EligibilityReport { fee_cap_too_low: true, ..Default::default() });
+    }
+
+    #[tokio::test]
+    async fn gas_per_calldata_byte() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+        let sign = |input: Bytes| {
+            sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_price: 10,
+                    gas_limit: 100_000,
+                    to: Call(Address::random()),
+                    input,
+                    ..Default::default()
+                }),
+            )
+        };
+        let with_data = sign(vec![1u8; 100].into());
+        let without_data = sign(Bytes::new());
+
+        let provider = MockEthProvider::default();
+        let block_hash = B256::random();
+        provider.add_block(
+            block_hash,
+            Block { body: vec![with_data.clone(), without_data.clone()], ..Default::default() },
+        );
+        provider.add_receipts(
+            block_hash,
+            vec![
+                Receipt { success: true, cumulative_gas_used: 50_000, ..Default::default() },
+                Receipt { success: true, cumulative_gas_used: 71_000, ..Default::default() },
+            ],
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        // 50000 gas at 10 wei for 100 bytes of calldata
+        assert_eq!(eth_api.gas_per_calldata_byte(with_data.hash).await.unwrap(), Some(5_000.0));
+        assert!(eth_api.gas_per_calldata_byte(without_data.hash).await.unwrap().is_none());
+        assert!(eth_api.gas_per_calldata_byte(B256::random()).await.unwrap().is_none());
     }
-}
 
-impl From<TransactionSource> for TransactionSignedEcRecovered {
-    fn from(value: TransactionSource) -> Self {
-        match value {
-            TransactionSource::Pool(tx) => tx,
-            TransactionSource::Block { transaction, .. } => transaction,
-        }
+    #[tokio::test]
+    async fn evm_env_for_pending_at() {
+        let provider = MockEthProvider::default();
+        let latest = Header {
+            number: 5,
+            timestamp: 1_000,
+            gas_limit: 30_000_000,
+            gas_used: 30_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            ..Default::default()
+        };
+        let latest_hash = B256::random();
+        provider.add_block(latest_hash, Block { header: latest.clone(), ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let (_, block_env, at) = eth_api.evm_env_for_pending_at(1_060).await.unwrap();
+        assert_eq!(at, BlockId::from(latest_hash));
+        assert_eq!(block_env.number, U256::from(6));
+        assert_eq!(block_env.timestamp, U256::from(1_060));
+        // the latest block is full, so the base fee increases by 12.5%
+        assert_eq!(block_env.basefee, U256::from(1_125_000_000));
+
+        assert!(eth_api.evm_env_for_pending_at(latest.timestamp).await.is_err());
     }
-}
 
-impl From<TransactionSource> for Transaction {
-    fn from(value: TransactionSource) -> Self {
-        match value {
-            TransactionSource::Pool(tx) => reth_rpc_types_compat::transaction::from_recovered(tx),
-            TransactionSource::Block { transaction, index, block_hash, block_number, base_fee } => {
-                from_recovered_with_block_context(
-                    transaction,
-                    block_hash,
-                    block_number,
-                    base_fee,
-                    U256::from(index),
+    #[tokio::test]
+    async fn global_transaction_index() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+        let txs = (0..4)
+            .map(|nonce| {
+                sign_tx_with_key_pair(
+                    key_pair,
+                    reth_primitives::Transaction::Legacy(TxLegacy {
+                        chain_id: Some(1),
+                        nonce,
+                        gas_price: 10,
+                        gas_limit: 21_000,
+                        to: Call(Address::random()),
+                        ..Default::default()
+                    }),
                 )
-            }
-        }
+            })
+            .collect::<Vec<_>>();
+
+        // the second block is inserted first, numbering must still follow the block numbers
+        let provider = MockEthProvider::default();
+        provider.add_block(
+            B256::random(),
+            Block {
+                header: Header { number: 1, ..Default::default() },
+                body: txs[2..].to_vec(),
+                ..Default::default()
+            },
+        );
+        provider.add_block(
+            B256::random(),
+            Block {
+                header: Header { number: 0, ..Default::default() },
+                body: txs[..2].to_vec(),
+                ..Default::default()
+            },
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        assert_eq!(eth_api.global_transaction_index(txs[0].hash).await.unwrap(), Some(0));
+        assert_eq!(eth_api.global_transaction_index(txs[1].hash).await.unwrap(), Some(1));
+        assert_eq!(eth_api.global_transaction_index(txs[3].hash).await.unwrap(), Some(3));
+        assert!(eth_api.global_transaction_index(B256::random()).await.unwrap().is_none());
     }
-}
 
-/// Helper function to construct a transaction receipt
-///
-/// Note: This requires _all_ block receipts because we need to calculate the gas used by the
-/// transaction.
-pub(crate) fn build_transaction_receipt_with_block_receipts(
-    transaction: TransactionSigned,
-    meta: TransactionMeta,
-    receipt: Receipt,
-    all_receipts: &[Receipt],
-    #[cfg(feature = "optimism")] optimism_tx_meta: OptimismTxMeta,
-) -> EthResult<TransactionReceipt> {
-    // Note: we assume this transaction is valid, because it's mined (or part of pending block) and
-    // we don't need to check for pre EIP-2
-    let from =
-        transaction.recover_signer_unchecked().ok_or(EthApiError::InvalidTransactionSignature)?;
+    #[tokio::test]
+    async fn transaction_by_sender_and_nonce() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+        let sign = |nonce| {
+            sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    nonce,
+                    gas_price: 100,
+                    gas_limit: 21_000,
+                    to: Call(Address::random()),
+                    ..Default::default()
+                }),
+            )
+        };
+        let pending = sign(0);
+        // leaves a nonce gap, so this one is queued
+        let queued = sign(2);
+        let sender = pending.recover_signer().unwrap();
 
-    // get the previous transaction cumulative gas used
-    let gas_used = if meta.index == 0 {
-        receipt.cumulative_gas_used
-    } else {
-        let prev_tx_idx = (meta.index - 1) as usize;
-        all_receipts
-            .get(prev_tx_idx)
-            .map(|prev_receipt| receipt.cumulative_gas_used - prev_receipt.cumulative_gas_used)
-            .unwrap_or_default()
-    };
+        let eth_api = build_test_eth_api(NoopProvider::default());
+        eth_api.send_raw_transaction(pending.envelope_encoded()).await.unwrap();
+        eth_api.send_raw_transaction(queued.envelope_encoded()).await.unwrap();
 
-    #[allow(clippy::needless_update)]
-    let mut res_receipt = TransactionReceipt {
-        transaction_hash: Some(meta.tx_hash),
-        transaction_index: U64::from(meta.index),
-        block_hash: Some(meta.block_hash),
-        block_number: Some(U256::from(meta.block_number)),
-        from,
-        to: None,
-        cumulative_gas_used: U256::from(receipt.cumulative_gas_used),
-        gas_used: Some(U256::from(gas_used)),
-        contract_address: None,
-        logs: Vec::with_capacity(receipt.logs.len()),
-        effective_gas_price: U128::from(transaction.effective_gas_price(meta.base_fee)),
-        transaction_type: transaction.transaction.tx_type().into(),
-        // TODO pre-byzantium receipts have a post-transaction state root
-        state_root: None,
-        logs_bloom: receipt.bloom_slow(),
-        status_code: if receipt.success { Some(U64::from(1)) } else { Some(U64::from(0)) },
-        // EIP-4844 fields
-        blob_gas_price: meta.excess_blob_gas.map(calc_blob_gasprice).map(U128::from),
-        blob_gas_used: transaction.transaction.blob_gas_used().map(U128::from),
-        // Optimism fields
-        #[cfg(feature = "optimism")]
-        deposit_nonce: receipt.deposit_nonce.map(U64::from),
-        ..Default::default()
-    };
+        let found = eth_api.transaction_by_sender_and_nonce(sender, 0).await.unwrap().unwrap();
+        assert!(matches!(&found, TransactionSource::Pool(_)));
+        assert_eq!(found.into_recovered().hash(), pending.hash);
 
-    #[cfg(feature = "optimism")]
-    if let Some(l1_block_info) = optimism_tx_meta.l1_block_info {
-        if !transaction.is_deposit() {
-            res_receipt.l1_fee = optimism_tx_meta.l1_fee;
-            res_receipt.l1_gas_used =
-                optimism_tx_meta.l1_data_gas.map(|dg| dg + l1_block_info.l1_fee_overhead);
-            res_receipt.l1_fee_scalar =
-                Some(l1_block_info.l1_fee_scalar.div(U256::from(1_000_000)));
-            res_receipt.l1_gas_price = Some(l1_block_info.l1_base_fee);
-        }
+        let found = eth_api.transaction_by_sender_and_nonce(sender, 2).await.unwrap().unwrap();
+        assert_eq!(found.into_recovered().hash(), queued.hash);
+
+        assert!(eth_api.transaction_by_sender_and_nonce(sender, 1).await.unwrap().is_none());
+        assert!(eth_api
+            .transaction_by_sender_and_nonce(Address::random(), 0)
+            .await
+            .unwrap()
+            .is_none());
     }
 
-    match transaction.transaction.kind() {
-        Create => {
-            res_receipt.contract_address = Some(from.create(transaction.transaction.nonce()));
-        }
-        Call(addr) => {
-            res_receipt.to = Some(*addr);
-        }
+    #[tokio::test]
+    async fn size_to_gas_ratio() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+        let sign = |nonce, input: Bytes| {
+            sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    nonce,
+                    gas_price: 10,
+                    gas_limit: 1_000_000,
+                    to: Call(Address::random()),
+                    input,
+                    ..Default::default()
+                }),
+            )
+        };
+        let data_heavy = sign(0, vec![1u8; 1_000].into());
+        let compute_heavy = sign(1, Bytes::new());
+
+        let provider = MockEthProvider::default();
+        let block_hash = B256::random();
+        provider.add_block(
+            block_hash,
+            Block { body: vec![data_heavy.clone(), compute_heavy.clone()], ..Default::default() },
+        );
+        provider.add_receipts(
+            block_hash,
+            vec![
+                Receipt { success: true, cumulative_gas_used: 37_000, ..Default::default() },
+                Receipt { success: true, cumulative_gas_used: 537_000, ..Default::default() },
+            ],
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        let data_ratio = eth_api.size_to_gas_ratio(data_heavy.hash).await.unwrap().unwrap();
+        let compute_ratio = eth_api.size_to_gas_ratio(compute_heavy.hash).await.unwrap().unwrap();
+        assert_eq!(data_ratio, data_heavy.envelope_encoded().len() as f64 / 37_000.0);
+        assert_eq!(compute_ratio, compute_heavy.envelope_encoded().len() as f64 / 500_000.0);
+        assert!(data_ratio > compute_ratio);
+        assert!(eth_api.size_to_gas_ratio(B256::random()).await.unwrap().is_none());
     }
 
-    // get number of logs in the block
-    let mut num_logs = 0;
-    for prev_receipt in all_receipts.iter().take(meta.index as usize) {
-        num_logs += prev_receipt.logs.len();
+    #[tokio::test]
+    async fn transact_call_with_fees_at() {
+        // PUSH1 0 PUSH1 0 SSTORE STOP
+        //
+        // clears slot 0, which is refunded
+        let contract = Address::random();
+        let caller = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Block::default());
+        provider.add_account(caller, ExtendedAccount::new(0, U256::from(1_000_000_000)));
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("600060005500")))
+                .extend_storage([(B256::ZERO, U256::from(1))]),
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        let request = CallRequest {
+            from: Some(caller),
+            to: Some(contract),
+            gas: Some(U256::from(100_000)),
+            max_fee_per_gas: Some(U256::from(100)),
+            max_priority_fee_per_gas: Some(U256::from(2)),
+            input: Bytes::from_static(&[1, 0]).into(),
+            ..Default::default()
+        };
+        let overrides = EvmOverrides::new(
+            None,
+            Some(Box::new(reth_rpc_types::BlockOverrides {
+                base_fee: Some(U256::from(10)),
+                ..Default::default()
+            })),
+        );
+
+        let (res, _, fees) = eth_api
+            .transact_call_with_fees_at(request, BlockNumberOrTag::Latest.into(), overrides)
+            .await
+            .unwrap();
+        assert!(res.result.is_success());
+        // base fee plus priority fee, below the max fee
+        assert_eq!(fees.effective_gas_price, U256::from(12));
+        // one non-zero and one zero calldata byte
+        assert_eq!(fees.intrinsic_gas, 21_000 + 16 + 4);
+        assert_eq!(fees.gas_refunded, 4_800);
+        assert_eq!(fees.gas_used, res.result.gas_used());
     }
 
-    for (tx_log_idx, log) in receipt.logs.into_iter().enumerate() {
-        let rpclog = Log {
-            address: log.address,
-            topics: log.topics,
-            data: log.data,
-            block_hash: Some(meta.block_hash),
-            block_number: Some(U256::from(meta.block_number)),
-            transaction_hash: Some(meta.tx_hash),
-            transaction_index: Some(U256::from(meta.index)),
-            log_index: Some(U256::from(num_logs + tx_log_idx)),
-            removed: false,
+    #[tokio::test]
+    async fn pool_top_gas_prices() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 5);
+        let eth_api = build_test_eth_api(NoopProvider::default());
+
+        for (key_pair, gas_price) in keys.into_iter().zip([10, 50, 30, 20, 40]) {
+            // zero gas limit so the unfunded senders can afford the transactions
+            let tx = sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_price,
+                    to: Call(Address::random()),
+                    ..Default::default()
+                }),
+            );
+            eth_api.send_raw_transaction(tx.envelope_encoded()).await.unwrap();
+        }
+
+        assert_eq!(
+            eth_api.pool_top_gas_prices(3).unwrap(),
+            vec![U256::from(50), U256::from(40), U256::from(30)]
+        );
+        assert_eq!(eth_api.pool_top_gas_prices(10).unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn is_gas_tight() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+        let sign = |nonce, gas_limit| {
+            sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    nonce,
+                    gas_price: 10,
+                    gas_limit,
+                    to: Call(Address::random()),
+                    ..Default::default()
+                }),
+            )
         };
-        res_receipt.logs.push(rpclog);
+        let tight = sign(0, 21_500);
+        let over_provisioned = sign(1, 100_000);
+
+        let provider = MockEthProvider::default();
+        let block_hash = B256::random();
+        provider.add_block(
+            block_hash,
+            Block { body: vec![tight.clone(), over_provisioned.clone()], ..Default::default() },
+        );
+        provider.add_receipts(
+            block_hash,
+            vec![
+                Receipt { success: true, cumulative_gas_used: 21_000, ..Default::default() },
+                Receipt { success: true, cumulative_gas_used: 42_000, ..Default::default() },
+            ],
+        );
+
+        let eth_api = build_test_eth_api(provider);
+
+        assert_eq!(eth_api.is_gas_tight(tight.hash, 5.0).await.unwrap(), Some(true));
+        assert_eq!(eth_api.is_gas_tight(over_provisioned.hash, 5.0).await.unwrap(), Some(false));
+        assert!(eth_api.is_gas_tight(B256::random(), 5.0).await.unwrap().is_none());
     }
 
-    Ok(res_receipt)
-}
+    #[tokio::test]
+    async fn opcode_coverage() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+
+        // PUSH1 0 CALLDATALOAD PUSH1 12 JUMPI PUSH1 1 PUSH1 0 SSTORE STOP JUMPDEST STOP
+        //
+        // jumps over the SSTORE if the first calldata word is non-zero
+        let contract = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("600035600c576001600055005b00"))),
+        );
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{
-        eth::{
-            cache::EthStateCache, gas_oracle::GasPriceOracle, FeeHistoryCache,
-            FeeHistoryCacheConfig,
-        },
-        BlockingTaskPool, EthApi,
-    };
-    use reth_network_api::noop::NoopNetwork;
-    use reth_primitives::{constants::ETHEREUM_BLOCK_GAS_LIMIT, hex_literal::hex, Bytes};
-    use reth_provider::test_utils::NoopProvider;
-    use reth_transaction_pool::{test_utils::testing_pool, TransactionPool};
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            reth_primitives::Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                gas_limit: 100_000,
+                to: Call(contract),
+                ..Default::default()
+            }),
+        );
+        let hash = tx.hash;
+        provider.add_block(B256::random(), Block { body: vec![tx], ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+
+        let report = eth_api.spawn_opcode_coverage(hash, contract).await.unwrap().unwrap();
+        // the jump is not taken, so the jump destination is never reached
+        assert_eq!(report.covered, vec![0, 2, 3, 5, 6, 8, 10, 11]);
+        assert_eq!(report.uncovered, vec![12, 13]);
+
+        // a contract that is not called isn't covered at all
+        let report = eth_api.spawn_opcode_coverage(hash, Address::random()).await.unwrap().unwrap();
+        assert_eq!(report, CoverageReport::default());
+
+        assert!(eth_api.spawn_opcode_coverage(B256::random(), contract).await.unwrap().is_none());
+    }
 
     #[tokio::test]
-    async fn send_raw_transaction() {
-        let noop_provider = NoopProvider::default();
-        let noop_network_provider = NoopNetwork::default();
+    async fn send_raw_transaction_with_origin() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+        let raw = |key_pair| {
+            sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_price: 100,
+                    gas_limit: 21_000,
+                    to: Call(Address::random()),
+                    ..Default::default()
+                }),
+            )
+            .envelope_encoded()
+        };
 
-        let pool = testing_pool();
+        let eth_api = build_test_eth_api(NoopProvider::default());
 
-        let cache = EthStateCache::spawn(noop_provider, Default::default());
-        let fee_history_cache =
-            FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
-        let eth_api = EthApi::new(
-            noop_provider,
-            pool.clone(),
-            noop_network_provider,
-            cache.clone(),
-            GasPriceOracle::new(noop_provider, Default::default(), cache.clone()),
-            ETHEREUM_BLOCK_GAS_LIMIT,
-            BlockingTaskPool::build().expect("failed to build tracing pool"),
-            fee_history_cache,
+        let external = eth_api
+            .send_raw_transaction_with_origin(raw(keys[0]), TransactionOrigin::External)
+            .await
+            .unwrap();
+        assert_eq!(eth_api.pool().get(&external).unwrap().origin, TransactionOrigin::External);
+
+        // without an explicit origin, transactions are local
+        let local = eth_api.send_raw_transaction(raw(keys[1])).await.unwrap();
+        assert_eq!(eth_api.pool().get(&local).unwrap().origin, TransactionOrigin::Local);
+    }
+
+    #[tokio::test]
+    async fn state_growth() {
+        let mut rng = generators::rng();
+        let key_pair = generate_keys(&mut rng, 1)[0];
+        let sign = |nonce, to, input| {
+            sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    nonce,
+                    gas_limit: 200_000,
+                    to,
+                    input,
+                    ..Default::default()
+                }),
+            )
+        };
+
+        // PUSH1 1 PUSH1 0 SSTORE PUSH1 0 PUSH1 0 MSTORE8 PUSH1 1 PUSH1 0 RETURN
+        //
+        // sets slot 0 of the new contract and deploys a single STOP
+        let deployment =
+            sign(0, Create, Bytes::from_static(&hex!("6001600055600060005360016000f3")));
+
+        // PUSH1 0 PUSH1 0 SSTORE PUSH1 0 PUSH1 1 SSTORE STOP
+        //
+        // clears slots 0 and 1
+        let clearing_contract = Address::random();
+        let clearing = sign(1, Call(clearing_contract), Bytes::new());
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            deployment.recover_signer().unwrap(),
+            ExtendedAccount::new(0, U256::from(1_000_000)),
+        );
+        provider.add_account(
+            clearing_contract,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("6000600055600060015500")))
+                .extend_storage([
+                    (B256::ZERO, U256::from(1)),
+                    (B256::with_last_byte(1), U256::from(1)),
+                ]),
+        );
+        provider.add_block(
+            B256::random(),
+            Block { body: vec![deployment.clone(), clearing.clone()], ..Default::default() },
         );
 
-        // https://etherscan.io/tx/0xa694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d
-        let tx_1 = Bytes::from(hex!("02f871018303579880850555633d1b82520894eee27662c2b8eba3cd936a23f039f3189633e4c887ad591c62bdaeb180c080a07ea72c68abfb8fca1bd964f0f99132ed9280261bdca3e549546c0205e800f7d0a05b4ef3039e9c9b9babc179a1878fb825b5aaf5aed2fa8744854150157b08d6f3"));
+        let eth_api = build_test_eth_api(provider);
 
-        let tx_1_result = eth_api.send_raw_transaction(tx_1).await.unwrap();
+        // the new contract with one byte of code and one slot
         assert_eq!(
-            pool.len(),
-            1,
-            "expect 1 transactions in the pool, but pool size is {}",
-            pool.len()
+            eth_api.spawn_state_growth(deployment.hash).await.unwrap(),
+            Some(ACCOUNT_STATE_BYTES + 1 + STORAGE_SLOT_STATE_BYTES)
+        );
+        assert_eq!(
+            eth_api.spawn_state_growth(clearing.hash).await.unwrap(),
+            Some(-2 * STORAGE_SLOT_STATE_BYTES)
         );
+        assert!(eth_api.spawn_state_growth(B256::random()).await.unwrap().is_none());
+    }
 
-        // https://etherscan.io/tx/0x48816c2f32c29d152b0d86ff706f39869e6c1f01dc2fe59a3c1f9ecf39384694
-        let tx_2 = Bytes::from(hex!("02f9043c018202b7843b9aca00850c807d37a08304d21d94ef1c6e67703c7bd7107eed8303fbe6ec2554bf6b881bc16d674ec80000b903c43593564c000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000063e2d99f00000000000000000000000000000000000000000000000000000000000000030b000800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000000c000000000000000000000000000000000000000000000000000000000000001e0000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000001bc16d674ec80000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000065717fe021ea67801d1088cc80099004b05b64600000000000000000000000000000000000000000000000001bc16d674ec80000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002bc02aaa39b223fe8d0a0e5c4f27ead9083c756cc20001f4a0b86991c6218b36c1d19d4a2e9eb0ce3606eb480000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000000180000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000009e95fd5965fd1f1a6f0d4600000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48000000000000000000000000428dca9537116148616a5a3e44035af17238fe9dc080a0c6ec1e41f5c0b9511c49b171ad4e04c6bb419c74d99fe9891d74126ec6e4e879a032069a753d7a2cfa158df95421724d24c0e9501593c09905abf3699b4a4405ce"));
+    #[tokio::test]
+    async fn call_under_spec_at() {
+        // PUSH1 0 SLOAD STOP
+        let contract = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Block::default());
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("60005400"))),
+        );
 
-        let tx_2_result = eth_api.send_raw_transaction(tx_2).await.unwrap();
-        assert_eq!(
-            pool.len(),
-            2,
-            "expect 2 transactions in the pool, but pool size is {}",
-            pool.len()
+        let eth_api = build_test_eth_api(provider);
+
+        let request = CallRequest { to: Some(contract), ..Default::default() };
+        let call = |spec| {
+            eth_api.call_under_spec_at(
+                request.clone(),
+                spec,
+                BlockNumberOrTag::Latest.into(),
+                EvmOverrides::default(),
+            )
+        };
+        let istanbul = call(SpecId::ISTANBUL).await.unwrap();
+        let berlin = call(SpecId::BERLIN).await.unwrap();
+
+        // a cold SLOAD costs 800 gas before and 2100 gas after Berlin
+        assert!(istanbul.result.is_success());
+        assert!(berlin.result.is_success());
+        assert_eq!(berlin.result.gas_used() - istanbul.result.gas_used(), 2100 - 800);
+    }
+
+    #[tokio::test]
+    async fn trace_call_many() {
+        // PUSH1 1 SLOAD PUSH1 12 JUMPI PUSH1 1 PUSH1 1 SSTORE STOP JUMPDEST PUSH1 0 PUSH1 0 REVERT
+        //
+        // sets slot 1 on the first call and reverts on every further call
+        let once = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Block::default());
+        provider.add_account(
+            once,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&hex!("600154600c576001600155005b60006000fd"))),
         );
 
-        assert!(pool.get(&tx_1_result).is_some(), "tx1 not found in the pool");
-        assert!(pool.get(&tx_2_result).is_some(), "tx2 not found in the pool");
+        let eth_api = build_test_eth_api(provider);
+
+        let request = CallRequest { to: Some(once), ..Default::default() };
+        let transfer = CallRequest { to: Some(Address::random()), ..Default::default() };
+        let results = eth_api
+            .trace_call_many(
+                vec![request.clone(), request, transfer],
+                BlockNumberOrTag::Latest.into(),
+                EvmOverrides::default(),
+                TracingInspectorConfig::default_parity(),
+            )
+            .await
+            .unwrap();
+
+        // the second call sees the slot set by the first one and reverts, which doesn't stop the
+        // bundle
+        assert_eq!(results.len(), 3);
+        let successes = results
+            .iter()
+            .map(|(inspector, res)| {
+                assert_eq!(
+                    inspector.get_traces().nodes()[0].trace.success,
+                    res.result.is_success()
+                );
+                res.result.is_success()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(successes, vec![true, false, true]);
+        assert!(matches!(results[1].1.result, ExecutionResult::Revert { .. }));
+    }
+
+    #[tokio::test]
+    async fn pending_dependency() {
+        let mut rng = generators::rng();
+        let keys = generate_keys(&mut rng, 2);
+        let sign = |key_pair, gas_price, to, value: U256| {
+            sign_tx_with_key_pair(
+                key_pair,
+                reth_primitives::Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    gas_price,
+                    gas_limit: 21_000,
+                    to: Call(to),
+                    value: value.into(),
+                    ..Default::default()
+                }),
+            )
+        };
+        let dependent = sign(keys[1], 50, Address::random(), U256::ZERO);
+        // the higher paying funding transaction is selected first
+        let funding = sign(
+            keys[0],
+            100,
+            dependent.recover_signer().unwrap(),
+            U256::from(1_000_000_000_000_000u64),
+        );
+
+        let provider = MockEthProvider::default();
+        provider.add_block(
+            B256::random(),
+            Block {
+                header: Header { gas_limit: ETHEREUM_BLOCK_GAS_LIMIT, ..Default::default() },
+                ..Default::default()
+            },
+        );
+        provider.add_account(
+            funding.recover_signer().unwrap(),
+            ExtendedAccount::new(0, U256::from(1_000_000_000_000_000_000u128)),
+        );
+
+        let eth_api = build_test_eth_api(provider);
+        eth_api.send_raw_transaction(funding.envelope_encoded()).await.unwrap();
+        eth_api.send_raw_transaction(dependent.envelope_encoded()).await.unwrap();
+
+        // the sender can only pay for the transaction after it was funded
+        assert_eq!(eth_api.spawn_pending_dependency(dependent.hash).await.unwrap(), Some(true));
+        assert_eq!(eth_api.spawn_pending_dependency(funding.hash).await.unwrap(), Some(false));
+        assert!(eth_api.spawn_pending_dependency(B256::random()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn recovered_block_transactions_are_cached() {
+        let mut rng = generators::rng();
+        let provider = MockEthProvider::default();
+        let body = generate_keys(&mut rng, 2)
+            .into_iter()
+            .map(|key_pair| {
+                sign_tx_with_key_pair(
+                    key_pair,
+                    reth_primitives::Transaction::Legacy(TxLegacy {
+                        chain_id: Some(1),
+                        gas_limit: 21_000,
+                        to: Call(Address::random()),
+                        ..Default::default()
+                    }),
+                )
+            })
+            .collect::<Vec<_>>();
+        let hashes = body.iter().map(|tx| tx.hash).collect::<Vec<_>>();
+        let block_hash = B256::random();
+        provider.add_block(block_hash, Block { body, ..Default::default() });
+
+        let eth_api = build_test_eth_api(provider);
+        assert!(eth_api.cached_recovered_transactions(block_hash).await.is_none());
+
+        let trace = |tx_info: TransactionInfo,
+                     _: TracingInspector,
+                     _: ExecutionResult,
+                     _: &State,
+                     _: &CacheDB<StateProviderDatabase<StateProviderBox>>| {
+            Ok(tx_info.hash.unwrap())
+        };
+        let config = TracingInspectorConfig::default_parity();
+
+        let traced =
+            eth_api.trace_block_with(block_hash.into(), config, trace).await.unwrap().unwrap();
+        assert_eq!(traced, hashes);
+        let recovered = eth_api.cached_recovered_transactions(block_hash).await.unwrap();
+        assert_eq!(recovered.iter().map(|tx| tx.hash).collect::<Vec<_>>(), hashes);
+
+        // tracing the block or a transaction of it again reuses the recovered transactions
+        let traced =
+            eth_api.trace_block_with(block_hash.into(), config, trace).await.unwrap().unwrap();
+        assert_eq!(traced, hashes);
+        eth_api
+            .spawn_trace_transaction_in_block(hashes[1], config, |tx_info, _, _, _| Ok(tx_info))
+            .await
+            .unwrap()
+            .unwrap();
+        let (_, transactions) =
+            eth_api.block_with_recovered_transactions(block_hash).await.unwrap().unwrap();
+        assert!(Arc::ptr_eq(&transactions, &recovered));
     }
 }
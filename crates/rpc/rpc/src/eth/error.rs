@@ -7,7 +7,7 @@ use jsonrpsee::{
     types::{error::CALL_EXECUTION_FAILED_CODE, ErrorObject},
 };
 use reth_interfaces::RethError;
-use reth_primitives::{revm_primitives::InvalidHeader, Address, Bytes, U256};
+use reth_primitives::{revm_primitives::InvalidHeader, Address, Bytes, B256, U256};
 use reth_revm::tracing::js::JsInspectorError;
 use reth_rpc_types::{error::EthRpcErrorCode, BlockError, CallInputError};
 use reth_transaction_pool::error::{
@@ -95,11 +95,29 @@ pub enum EthApiError {
     /// Error thrown when a (tracing) call exceeded the configured timeout.
     #[error("execution aborted (timeout = {0:?})")]
     ExecutionTimedOut(Duration),
+    /// Error thrown when a trace exceeded the configured step or memory limit.
+    #[error("tracing aborted: configured step or memory limit exceeded")]
+    TracingTimeoutOrLimit,
+    /// Error thrown when a blocking tracing task exceeded the configured timeout.
+    #[error("tracing aborted (timeout = {0:?})")]
+    TracingTimeout(Duration),
     /// Internal Error thrown by the javascript tracer
     #[error("{0}")]
     InternalJsTracerError(String),
     #[error(transparent)]
     CallInputError(#[from] CallInputError),
+    /// Thrown when the execution of a call reverted, see [EthApiError::reverted].
+    #[error(
+        "execution reverted{}",
+        reason.as_ref().map(|reason| format!(": {reason}")).unwrap_or_default()
+    )]
+    ExecutionReverted {
+        /// The revert reason decoded from a standard Solidity `Error(string)` or
+        /// `Panic(uint256)` output.
+        reason: Option<String>,
+        /// The raw output of the call, `None` if the output was empty.
+        data: Option<Bytes>,
+    },
     /// Optimism related error
     #[error(transparent)]
     #[cfg(feature = "optimism")]
@@ -116,6 +134,22 @@ pub enum OptimismEthApiError {
     /// Wrapper around an [http::Error].
     #[error(transparent)]
     HttpError(#[from] http::Error),
+    /// Thrown when the sequencer could not be reached.
+    #[error(transparent)]
+    SequencerRequestError(#[from] reqwest::Error),
+    /// Thrown when the sequencer responded with an unsuccessful HTTP status.
+    #[error("sequencer responded with status {0}")]
+    SequencerStatus(u16),
+    /// The JSON-RPC error returned by the sequencer.
+    #[error("{message}")]
+    SequencerError {
+        /// The error code returned by the sequencer.
+        code: i32,
+        /// The error message returned by the sequencer.
+        message: String,
+        /// Additional error data returned by the sequencer, if any.
+        data: Option<serde_json::Value>,
+    },
 }
 
 impl From<EthApiError> for ErrorObject<'static> {
@@ -149,18 +183,43 @@ impl From<EthApiError> for ErrorObject<'static> {
             err @ EthApiError::ExecutionTimedOut(_) => {
                 rpc_error_with_code(CALL_EXECUTION_FAILED_CODE, err.to_string())
             }
+            err @ (EthApiError::TracingTimeoutOrLimit | EthApiError::TracingTimeout(_)) => {
+                rpc_error_with_code(CALL_EXECUTION_FAILED_CODE, err.to_string())
+            }
             err @ EthApiError::InternalBlockingTaskError => internal_rpc_err(err.to_string()),
             err @ EthApiError::InternalEthError => internal_rpc_err(err.to_string()),
             err @ EthApiError::CallInputError(_) => invalid_params_rpc_err(err.to_string()),
+            EthApiError::ExecutionReverted { ref data, .. } => rpc_err(
+                EthRpcErrorCode::ExecutionError.code(),
+                error.to_string(),
+                data.as_ref().map(|data| data.as_ref()),
+            ),
             #[cfg(feature = "optimism")]
             EthApiError::Optimism(err) => match err {
                 OptimismEthApiError::HyperError(err) => internal_rpc_err(err.to_string()),
                 OptimismEthApiError::HttpError(err) => internal_rpc_err(err.to_string()),
+                err @ (OptimismEthApiError::SequencerRequestError(_) |
+                OptimismEthApiError::SequencerStatus(_)) => internal_rpc_err(err.to_string()),
+                OptimismEthApiError::SequencerError { code, message, data } => {
+                    ErrorObject::owned(code, message, data)
+                }
             },
         }
     }
 }
 
+impl EthApiError {
+    /// Creates an [EthApiError::ExecutionReverted] from the output of a reverted call.
+    ///
+    /// Standard Solidity `Error(string)` and `Panic(uint256)` outputs are decoded into a human
+    /// readable reason, the raw output is kept as data.
+    pub fn reverted(output: Bytes) -> Self {
+        let reason = decode_revert_output(&output);
+        let data = (!output.is_empty()).then_some(output);
+        EthApiError::ExecutionReverted { reason, data }
+    }
+}
+
 impl From<EthApiError> for RpcError {
     fn from(error: EthApiError) -> Self {
         RpcError::Call(error.into())
@@ -553,8 +612,9 @@ impl std::error::Error for RevertError {}
 #[derive(Debug, thiserror::Error)]
 #[allow(missing_docs)]
 pub enum RpcPoolError {
+    /// The transaction is already in the pool, contains the hash of the pooled transaction.
     #[error("already known")]
-    AlreadyKnown,
+    AlreadyKnown(B256),
     #[error("invalid sender")]
     InvalidSender,
     #[error("transaction underpriced")]
@@ -593,6 +653,13 @@ impl From<RpcPoolError> for ErrorObject<'static> {
     fn from(error: RpcPoolError) -> Self {
         match error {
             RpcPoolError::Invalid(err) => err.into(),
+            // the hash of the pooled transaction is returned as data, so that a resubmission can
+            // be treated as a success
+            RpcPoolError::AlreadyKnown(hash) => rpc_err(
+                jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                "already known",
+                Some(hash.as_slice()),
+            ),
             error => internal_rpc_err(error.to_string()),
         }
     }
@@ -607,7 +674,7 @@ impl From<PoolError> for RpcPoolError {
             PoolErrorKind::DiscardedOnInsert => RpcPoolError::TxPoolOverflow,
             PoolErrorKind::InvalidTransaction(err) => err.into(),
             PoolErrorKind::Other(err) => RpcPoolError::Other(err),
-            PoolErrorKind::AlreadyImported => RpcPoolError::AlreadyKnown,
+            PoolErrorKind::AlreadyImported => RpcPoolError::AlreadyKnown(err.hash),
             PoolErrorKind::ExistingConflictingTransactionType(_, _) => {
                 RpcPoolError::AddressAlreadyReserved
             }
@@ -663,14 +730,29 @@ pub enum SignError {
     NoChainId,
 }
 
+/// Selector of the Solidity `Panic(uint256)` error.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Decodes the output of a reverted call into a human readable reason.
+///
+/// `Panic(uint256)` outputs are decoded into their panic code, all other outputs are decoded with
+/// [decode_revert_reason], which handles `Error(string)` and UTF-8 encoded outputs.
+pub(crate) fn decode_revert_output(output: &[u8]) -> Option<String> {
+    if output.len() == 36 && output[..4] == PANIC_SELECTOR {
+        let code = U256::from_be_slice(&output[4..]);
+        return Some(format!("panic code 0x{code:x}"))
+    }
+    decode_revert_reason(output)
+}
+
 /// Converts the evm [ExecutionResult] into a result where `Ok` variant is the output bytes if it is
 /// [ExecutionResult::Success].
+///
+/// A revert is converted into an [EthApiError::ExecutionReverted] with the decoded revert reason.
 pub(crate) fn ensure_success(result: ExecutionResult) -> EthResult<Bytes> {
     match result {
         ExecutionResult::Success { output, .. } => Ok(output.into_data()),
-        ExecutionResult::Revert { output, .. } => {
-            Err(RpcInvalidTransactionError::Revert(RevertError::new(output)).into())
-        }
+        ExecutionResult::Revert { output, .. } => Err(EthApiError::reverted(output)),
         ExecutionResult::Halt { reason, gas_used } => {
             Err(RpcInvalidTransactionError::halt(reason, gas_used).into())
         }
@@ -686,4 +768,54 @@ mod tests {
         let err = EthApiError::ExecutionTimedOut(Duration::from_secs(10));
         assert_eq!(err.to_string(), "execution aborted (timeout = 10s)");
     }
+
+    #[test]
+    fn pool_nonce_too_low_error() {
+        let err = EthApiError::from(PoolError::new(
+            B256::ZERO,
+            InvalidPoolTransactionError::Consensus(
+                reth_primitives::InvalidTransactionError::NonceNotConsistent,
+            ),
+        ));
+        assert_eq!(err.to_string(), "nonce too low");
+    }
+
+    #[test]
+    fn pool_already_known_error() {
+        let hash = B256::with_last_byte(1);
+        let err = EthApiError::from(PoolError::new(hash, PoolErrorKind::AlreadyImported));
+        assert!(matches!(err, EthApiError::PoolError(RpcPoolError::AlreadyKnown(h)) if h == hash));
+
+        let err = ErrorObject::from(err);
+        assert_eq!(err.message(), "already known");
+        assert_eq!(err.data().unwrap().get(), format!("\"{hash}\""));
+    }
+
+    #[test]
+    fn execution_reverted_error() {
+        // Error("ERC20: insufficient balance")
+        let mut output = vec![0x08, 0xc3, 0x79, 0xa0];
+        output.extend_from_slice(&U256::from(32).to_be_bytes::<32>());
+        output.extend_from_slice(&U256::from(27).to_be_bytes::<32>());
+        let mut reason = [0u8; 32];
+        reason[..27].copy_from_slice(b"ERC20: insufficient balance");
+        output.extend_from_slice(&reason);
+        let output = Bytes::from(output);
+
+        let err = EthApiError::reverted(output.clone());
+        assert_eq!(err.to_string(), "execution reverted: ERC20: insufficient balance");
+        let err = ErrorObject::from(err);
+        assert_eq!(err.code(), EthRpcErrorCode::ExecutionError.code());
+        assert_eq!(err.data().unwrap().get(), format!("\"{output}\""));
+
+        // Panic(0x11), an arithmetic overflow
+        let mut output = PANIC_SELECTOR.to_vec();
+        output.extend_from_slice(&U256::from(0x11).to_be_bytes::<32>());
+        let err = EthApiError::reverted(output.into());
+        assert_eq!(err.to_string(), "execution reverted: panic code 0x11");
+
+        let err = EthApiError::reverted(Bytes::new());
+        assert!(matches!(err, EthApiError::ExecutionReverted { reason: None, data: None }));
+        assert_eq!(err.to_string(), "execution reverted");
+    }
 }
@@ -15,7 +15,12 @@ pub(crate) mod utils;
 
 pub use api::{
     fee_history::{fee_history_cache_new_blocks_task, FeeHistoryCache, FeeHistoryCacheConfig},
-    EthApi, EthApiSpec, EthTransactions, TransactionSource, RPC_DEFAULT_GAS_CAP,
+    AccessSet, AdjacentReceipts, CallFees, CallOutcome, CoverageReport, EligibilityReport, EthApi,
+    EthApiSpec, EthTransactions, FeeBreakdown, FeeSuggestion, GasStats, JumpPoint,
+    SequencerRetryConfig, SimulatedBlock, SimulatedCall, TracingLimits, TransactionSource,
+    TransactionStatus, TxFee, TxOutcome, ACCOUNT_STATE_BYTES, DEFAULT_MAX_RECOVERED_BLOCKS,
+    DEFAULT_TRACING_TIMEOUT, MAX_ORDERING_TRANSACTIONS, RPC_DEFAULT_GAS_CAP, SIMULATED_BLOCK_TIME,
+    STORAGE_SLOT_STATE_BYTES,
 };
 
 pub use bundle::EthBundle;
@@ -45,6 +45,11 @@ impl EvmOverrides {
         Self { state, block: None }
     }
 
+    /// Creates a new instance with the given block overrides.
+    pub fn block(block: Option<Box<BlockOverrides>>) -> Self {
+        Self { state: None, block }
+    }
+
     /// Returns `true` if the overrides contain state overrides.
     pub fn has_state(&self) -> bool {
         self.state.is_some()
@@ -227,20 +232,7 @@ where
 
     let mut env = build_call_evm_env(cfg, block, request)?;
 
-    // apply state overrides
-    if let Some(state_overrides) = overrides.state {
-        apply_state_overrides(state_overrides, db)?;
-    }
-
-    // apply block overrides
-    if let Some(mut block_overrides) = overrides.block {
-        if let Some(block_hashes) = block_overrides.block_hash.take() {
-            // override block hashes
-            db.block_hashes
-                .extend(block_hashes.into_iter().map(|(num, hash)| (U256::from(num), hash)))
-        }
-        apply_block_overrides(*block_overrides, &mut env.block);
-    }
+    apply_evm_overrides(overrides, db, &mut env.block)?;
 
     if request_gas.is_none() {
         // No gas limit was provided in the request, so we need to cap the transaction gas limit
@@ -483,8 +475,38 @@ impl CallFees {
     }
 }
 
+/// Applies the state overrides to the [CacheDB] and the block overrides to the [BlockEnv].
+///
+/// Block hash overrides are applied to the [CacheDB].
+pub(crate) fn apply_evm_overrides<DB>(
+    overrides: EvmOverrides,
+    db: &mut CacheDB<DB>,
+    block: &mut BlockEnv,
+) -> EthResult<()>
+where
+    DB: DatabaseRef,
+    EthApiError: From<<DB as DatabaseRef>::Error>,
+{
+    // apply state overrides
+    if let Some(state_overrides) = overrides.state {
+        apply_state_overrides(state_overrides, db)?;
+    }
+
+    // apply block overrides
+    if let Some(mut block_overrides) = overrides.block {
+        if let Some(block_hashes) = block_overrides.block_hash.take() {
+            // override block hashes
+            db.block_hashes
+                .extend(block_hashes.into_iter().map(|(num, hash)| (U256::from(num), hash)))
+        }
+        apply_block_overrides(*block_overrides, block);
+    }
+
+    Ok(())
+}
+
 /// Applies the given block overrides to the env
-fn apply_block_overrides(overrides: BlockOverrides, env: &mut BlockEnv) {
+pub(crate) fn apply_block_overrides(overrides: BlockOverrides, env: &mut BlockEnv) {
     let BlockOverrides {
         number,
         difficulty,
@@ -603,9 +625,26 @@ where
     }
 }
 
+/// Creates a new [CacheDB] on top of the given [DatabaseRef] that is pre-populated with the
+/// cached state of the given [CacheDB].
+///
+/// This is the counterpart of [clone_into_empty_db].
+#[inline]
+pub(crate) fn with_cached_state<DB>(cached: CacheDB<EmptyDB>, db: DB) -> CacheDB<DB>
+where
+    DB: DatabaseRef,
+{
+    let CacheDB { accounts, contracts, logs, block_hashes, .. } = cached;
+    CacheDB { accounts, contracts, logs, block_hashes, db }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use reth_primitives::Bytes;
+    use reth_provider::test_utils::NoopProvider;
+    use reth_revm::database::StateProviderDatabase;
+    use revm::primitives::AccountInfo;
 
     #[test]
     fn test_ensure_0_fallback() {
@@ -636,4 +675,46 @@ mod tests {
         assert!(gas_price.is_zero());
         assert_eq!(max_fee_per_blob_gas, Some(U256::from(99)));
     }
+
+    #[test]
+    fn test_call_with_coinbase_override() {
+        let mut db = CacheDB::new(StateProviderDatabase::new(NoopProvider::default()));
+
+        // CALL(gas, COINBASE, 1, 0, 0, 0, 0)
+        let code = Bytes::from_static(&[
+            0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x01, 0x41, 0x5a, 0xf1, 0x00,
+        ]);
+        let contract = Address::with_last_byte(0xaa);
+        db.insert_account_info(
+            contract,
+            AccountInfo {
+                balance: U256::from(1),
+                code: Some(Bytecode::new_raw(code)),
+                ..Default::default()
+            },
+        );
+
+        let coinbase = Address::with_last_byte(0xcb);
+        let overrides = EvmOverrides::block(Some(Box::new(BlockOverrides {
+            coinbase: Some(coinbase),
+            ..Default::default()
+        })));
+        let request = CallRequest { to: Some(contract), ..Default::default() };
+
+        let env = prepare_call_env(
+            CfgEnv::default(),
+            BlockEnv::default(),
+            request,
+            30_000_000,
+            &mut db,
+            overrides,
+        )
+        .unwrap();
+        assert_eq!(env.block.coinbase, coinbase);
+
+        let (ResultAndState { result, state }, _) = transact(&mut db, env).unwrap();
+        assert!(result.is_success());
+        assert_eq!(state[&coinbase].info.balance, U256::from(1));
+        assert_eq!(state[&contract].info.balance, U256::ZERO);
+    }
 }
@@ -107,13 +107,19 @@ where
 
         self.inner
             .eth_api
-            .spawn_trace_at_with_state(env, config, at, move |inspector, res, db| {
-                Ok(inspector.into_parity_builder().into_trace_results_with_state(
-                    &res,
-                    &trace_types,
-                    &db,
-                )?)
-            })
+            .spawn_trace_at_with_state(
+                env,
+                config,
+                at,
+                Default::default(),
+                move |inspector, res, db| {
+                    Ok(inspector.into_parity_builder().into_trace_results_with_state(
+                        &res,
+                        &trace_types,
+                        &db,
+                    )?)
+                },
+            )
             .await
     }
 
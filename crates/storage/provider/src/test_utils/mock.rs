@@ -30,8 +30,12 @@ pub struct MockEthProvider {
     pub blocks: Arc<Mutex<HashMap<B256, Block>>>,
     /// Local header store
     pub headers: Arc<Mutex<HashMap<B256, Header>>>,
+    /// Local receipt store indexed by block hash
+    pub receipts: Arc<Mutex<HashMap<B256, Vec<Receipt>>>>,
     /// Local account store
     pub accounts: Arc<Mutex<HashMap<Address, ExtendedAccount>>>,
+    /// Local account store of the pending state, applied on top of `accounts`
+    pub pending_accounts: Arc<Mutex<HashMap<Address, ExtendedAccount>>>,
     /// Local chain spec
     pub chain_spec: Arc<ChainSpec>,
 }
@@ -41,7 +45,9 @@ impl Default for MockEthProvider {
         MockEthProvider {
             blocks: Default::default(),
             headers: Default::default(),
+            receipts: Default::default(),
             accounts: Default::default(),
+            pending_accounts: Default::default(),
             chain_spec: Arc::new(reth_primitives::ChainSpecBuilder::mainnet().build()),
         }
     }
@@ -111,6 +117,11 @@ impl MockEthProvider {
         }
     }
 
+    /// Add receipts of the block with the given hash to local receipt store
+    pub fn add_receipts(&self, block_hash: B256, receipts: Vec<Receipt>) {
+        self.receipts.lock().insert(block_hash, receipts);
+    }
+
     /// Add account to local account store
     pub fn add_account(&self, address: Address, account: ExtendedAccount) {
         self.accounts.lock().insert(address, account);
@@ -122,6 +133,28 @@ impl MockEthProvider {
             self.add_account(address, account)
         }
     }
+
+    /// Add account to the local account store of the pending state
+    pub fn add_pending_account(&self, address: Address, account: ExtendedAccount) {
+        self.pending_accounts.lock().insert(address, account);
+    }
+
+    /// Returns the transactions of all local blocks in the order they are numbered in, which is
+    /// ordered by block number.
+    fn numbered_transactions(&self) -> Vec<TransactionSigned> {
+        let lock = self.blocks.lock();
+        let mut blocks = lock.values().collect::<Vec<_>>();
+        blocks.sort_by_key(|block| block.number);
+        blocks.into_iter().flat_map(|block| block.body.clone()).collect()
+    }
+
+    /// Returns a provider for the pending state, which is the local account store with the
+    /// pending accounts applied on top.
+    fn pending_state(&self) -> Self {
+        let mut accounts = self.accounts.lock().clone();
+        accounts.extend(self.pending_accounts.lock().clone());
+        Self { accounts: Arc::new(Mutex::new(accounts)), ..self.clone() }
+    }
 }
 
 impl HeaderProvider for MockEthProvider {
@@ -189,10 +222,9 @@ impl ChainSpecProvider for MockEthProvider {
 
 impl TransactionsProvider for MockEthProvider {
     fn transaction_id(&self, tx_hash: TxHash) -> ProviderResult<Option<TxNumber>> {
-        let lock = self.blocks.lock();
-        let tx_number = lock
-            .values()
-            .flat_map(|block| &block.body)
+        let tx_number = self
+            .numbered_transactions()
+            .iter()
             .position(|tx| tx.hash() == tx_hash)
             .map(|pos| pos as TxNumber);
 
@@ -200,8 +232,7 @@ impl TransactionsProvider for MockEthProvider {
     }
 
     fn transaction_by_id(&self, id: TxNumber) -> ProviderResult<Option<TransactionSigned>> {
-        let lock = self.blocks.lock();
-        let transaction = lock.values().flat_map(|block| &block.body).nth(id as usize).cloned();
+        let transaction = self.numbered_transactions().into_iter().nth(id as usize);
 
         Ok(transaction)
     }
@@ -210,12 +241,11 @@ impl TransactionsProvider for MockEthProvider {
         &self,
         id: TxNumber,
     ) -> ProviderResult<Option<TransactionSignedNoHash>> {
-        let lock = self.blocks.lock();
-        let transaction = lock
-            .values()
-            .flat_map(|block| &block.body)
+        let transaction = self
+            .numbered_transactions()
+            .into_iter()
             .nth(id as usize)
-            .map(|tx| Into::<TransactionSignedNoHash>::into(tx.clone()));
+            .map(Into::<TransactionSignedNoHash>::into);
 
         Ok(transaction)
     }
@@ -337,12 +367,24 @@ impl ReceiptProvider for MockEthProvider {
         Ok(None)
     }
 
-    fn receipt_by_hash(&self, _hash: TxHash) -> ProviderResult<Option<Receipt>> {
-        Ok(None)
+    fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Receipt>> {
+        let Some((_, meta)) = self.transaction_by_hash_with_meta(hash)? else { return Ok(None) };
+        let lock = self.receipts.lock();
+        Ok(lock
+            .get(&meta.block_hash)
+            .and_then(|receipts| receipts.get(meta.index as usize))
+            .cloned())
     }
 
-    fn receipts_by_block(&self, _block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
-        Ok(None)
+    fn receipts_by_block(&self, block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
+        let block_hash = match block {
+            BlockHashOrNumber::Hash(hash) => hash,
+            BlockHashOrNumber::Number(num) => match self.block_hash(num)? {
+                Some(hash) => hash,
+                None => return Ok(None),
+            },
+        };
+        Ok(self.receipts.lock().get(&block_hash).cloned())
     }
 }
 
@@ -456,10 +498,12 @@ impl BlockReader for MockEthProvider {
 
     fn block_with_senders(
         &self,
-        _id: BlockHashOrNumber,
+        id: BlockHashOrNumber,
         _transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<BlockWithSenders>> {
-        Ok(None)
+        Ok(self
+            .block(id)?
+            .and_then(|block| block.senders().map(|senders| BlockWithSenders { block, senders })))
     }
 
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>> {
@@ -608,11 +652,11 @@ impl StateProviderFactory for MockEthProvider {
     }
 
     fn pending(&self) -> ProviderResult<StateProviderBox> {
-        Ok(Box::new(self.clone()))
+        Ok(Box::new(self.pending_state()))
     }
 
     fn pending_state_by_hash(&self, _block_hash: B256) -> ProviderResult<Option<StateProviderBox>> {
-        Ok(Some(Box::new(self.clone())))
+        Ok(Some(Box::new(self.pending_state())))
     }
 
     fn pending_with_provider<'a>(
@@ -641,11 +685,11 @@ impl StateProviderFactory for Arc<MockEthProvider> {
     }
 
     fn pending(&self) -> ProviderResult<StateProviderBox> {
-        Ok(Box::new(self.clone()))
+        Ok(Box::new(self.pending_state()))
     }
 
     fn pending_state_by_hash(&self, _block_hash: B256) -> ProviderResult<Option<StateProviderBox>> {
-        Ok(Some(Box::new(self.clone())))
+        Ok(Some(Box::new(self.pending_state())))
     }
 
     fn pending_with_provider<'a>(